@@ -9,21 +9,13 @@ fn main() {
 		.stop_bits(StopBits::One)
 		.flow_control(FlowControl::None);
 
-    let mut board = firmata_client::Board::new(serial_port_builder);
-
-	while !board.is_ready() {
-		board.poll().expect("successful polling");
-		println!("waiting...");
-        thread::sleep(Duration::from_millis(100));
-	}
+    let mut board = firmata_client::Board::connect(serial_port_builder, Duration::from_secs(5)).expect("board connected");
 	println!("setup complete");
 
     let led = 5;
-    let pin = 14; // A0
 
-    board.set_pin_mode(led, firmata_client::PIN_MODE_PWM).expect("pin mode set");
-    board.set_pin_mode(pin, firmata_client::PIN_MODE_ANALOG).expect("pin mode set");
-    board.report_analog(pin, true).expect("reporting state");
+    board.set_pin_mode(led, firmata_client::PinMode::Pwm).expect("pin mode set");
+    let pin = board.analog_input("A0").expect("pin mode set").pin();
 
     loop {
         board.poll()
@@ -34,7 +26,8 @@ fn main() {
             .for_each(|(pin_index, value)|{
                 println!("analog pin {pin_index} value: {value}");
                 if pin_index == &pin {
-                    board.analog_write(led, *value).expect("digital write");
+                    let level = board.read_normalized(pin).expect("analog read");
+                    board.write_normalized(led, level).expect("analog write");
                 }
             });
 
@@ -0,0 +1,41 @@
+use std::time::Duration;
+
+use serialport::*;
+use tokio_stream::StreamExt;
+
+use firmata_client::{AsyncBoard, Message};
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+	let runtime = tokio::runtime::Builder::new_current_thread()
+		.enable_all()
+		.build()
+		.expect("runtime built");
+
+	runtime.block_on(async {
+		let serial_port_builder = serialport::new("/dev/tty.usbmodem14201", 57_600)
+			.data_bits(DataBits::Eight)
+			.parity(Parity::None)
+			.stop_bits(StopBits::One)
+			.flow_control(FlowControl::None);
+
+		let board = AsyncBoard::connect_serial(serial_port_builder, Duration::from_secs(5)).await.expect("board connected");
+		println!("setup complete");
+
+		let (name, version) = board.firmware(Duration::from_secs(1)).await.expect("firmware");
+		println!("firmware: {name} {version}");
+
+		let pin = board.query_pin_state(13, Duration::from_secs(1)).await.expect("pin state");
+		println!("pin 13: {} {}", pin.mode, pin.value);
+
+		board.with_board(|board| board.report_digital(2, true)).await.expect("digital reporting on");
+
+		let mut messages = board.messages();
+		while let Some(message) = messages.next().await {
+			if let Message::Digital(pins) = message {
+				println!("digital: {pins:?}");
+			}
+		}
+	});
+}
@@ -11,23 +11,17 @@ fn main() {
 		.stop_bits(StopBits::One)
 		.flow_control(FlowControl::None);
 
-    let mut board = firmata_client::Board::new(serial_port_builder);
-	while !board.is_ready() {
-		board.poll().expect("successful polling");
-		println!("waiting...");
-        thread::sleep(Duration::from_millis(100));
-	}
+    let mut board = firmata_client::Board::connect(serial_port_builder, Duration::from_secs(5)).expect("board connected");
 	println!("setup complete");
 
-	let pin = 13;
-    board.set_pin_mode(pin, firmata_client::PIN_MODE_OUTPUT).expect("pin mode set");
+    let led = board.digital_output(13).expect("pin mode set");
 
     let mut state = false;
 
     loop {
         thread::sleep(Duration::from_millis(1000));
 		println!(">> {state}");
-        board.digital_write(pin, state).expect("digital write");
+        led.write(&mut board, state).expect("digital write");
         state = !state;
     }
 }
@@ -1,82 +1,54 @@
-// use std::io::{Read, Write};
-// use std::sync::{Arc, Mutex};
-// use std::thread;
-// use std::time::Duration;
+use std::{thread, time::Duration};
 
-// use serialport::*;
+use serialport::*;
 
-// use firmata_client::Board;
+use firmata_client::{Board, SharedBoard};
 
-// fn init<T: Read + Write + std::fmt::Debug>(board: &Arc<Mutex<Board>>) {
-//     let mut b = board.lock().expect("lock");
-//     b.i2c_config(0).expect("i2c delay set");
-//     b.i2c_write(0x09, "o".as_bytes()).expect("i2c write");
-//     thread::sleep(Duration::from_millis(10));
-// }
+const BLINKM: u8 = 0x09;
 
-// fn set_rgb<T: Read + Write + std::fmt::Debug>(board: &Arc<Mutex<Board>>, rgb: [u8; 3]) {
-//     let mut b = board.lock().expect("lock");
-//     b.i2c_write(0x09, "n".as_bytes()).expect("i2c write");
-//     b.i2c_write(0x09, &rgb).expect("i2c write");
-// }
+fn init(board: &mut Board) {
+    board.i2c_config(0).expect("i2c delay set");
+    board.i2c_write(BLINKM, b"o").expect("i2c write");
+    thread::sleep(Duration::from_millis(10));
+}
 
-// fn read_rgb<T: Read + Write + std::fmt::Debug>(board: &Arc<Mutex<Board>>) -> Vec<u8> {
-// 	let mut b = board.lock().expect("lock");
+fn set_rgb(board: &mut Board, rgb: [u8; 3]) {
+    board.i2c_write(BLINKM, &[b'n', rgb[0], rgb[1], rgb[2]]).expect("i2c write");
+}
 
-// 	b.i2c_write(0x09, "g".as_bytes()).expect("i2c write");
-// 	b.i2c_read(0x09, 3).expect("i2c read");
-
-// 	loop {
-// 		if let Some(i2c_reply) = b.get_i2c_data().last() {
-// 			return i2c_reply.data.clone();
-// 		}
-//         thread::sleep(Duration::from_millis(10));
-// 	}
-// }
+fn read_rgb(board: &mut Board) -> Vec<u8> {
+    board
+        .i2c_read_register(BLINKM, b'g', 3, Duration::from_millis(500))
+        .expect("i2c read")
+        .data
+}
 
 fn main() {
-//     tracing_subscriber::fmt::init();
-
-// 	let serial_port_builder = serialport::new("/dev/tty.usbmodem14201", 57_600)
-// 		.data_bits(DataBits::Eight)
-// 		.parity(Parity::None)
-// 		.stop_bits(StopBits::One)
-// 		.flow_control(FlowControl::None)
-// 		.timeout(Duration::from_millis(10000));
-//     let port = serial_port_builder.clone()
-//         .open()
-//         .expect("an opened serial port");
-
-//     let board = Arc::new(Mutex::new(
-//         firmata_client::Board::new(serial_port_builder).expect("new board"),
-//     ));
-
-//     {
-//         let b = board.clone();
-//         thread::spawn(move || loop {
-//             b.lock()
-//                 .expect("lock")
-//                 .poll()
-//                 .expect("a message");
-//             b.lock()
-//                 .expect("lock")
-//                 .report_firmware()
-//                 .expect("firmware and protocol info");
-//             thread::sleep(Duration::from_millis(10));
-//         });
-//     }
-
-//     init(&board);
-
-//     set_rgb(&board, [255, 0, 0]);
-//     tracing::info!("rgb: {:?}", read_rgb(&board));
-//     thread::sleep(Duration::from_millis(1000));
-
-//     set_rgb(&board, [0, 255, 0]);
-//     tracing::info!("rgb: {:?}", read_rgb(&board));
-//     thread::sleep(Duration::from_millis(1000));
-
-//     set_rgb(&board, [0, 0, 255]);
-//     tracing::info!("rgb: {:?}", read_rgb(&board));
-//     thread::sleep(Duration::from_millis(1000));
-}
\ No newline at end of file
+    tracing_subscriber::fmt::init();
+
+	let serial_port_builder = serialport::new("/dev/tty.usbmodem14201", 57_600)
+		.data_bits(DataBits::Eight)
+		.parity(Parity::None)
+		.stop_bits(StopBits::One)
+		.flow_control(FlowControl::None);
+
+    let board = SharedBoard::connect(serial_port_builder, Duration::from_secs(5)).expect("board connected");
+	println!("setup complete");
+
+    board.with_board(init);
+
+    let writer = {
+        let board = board.clone();
+        thread::spawn(move || {
+            for rgb in [[255, 0, 0], [0, 255, 0], [0, 0, 255]] {
+                board.with_board(|board| set_rgb(board, rgb));
+                thread::sleep(Duration::from_millis(1000));
+            }
+        })
+    };
+
+    while !writer.is_finished() {
+        tracing::info!("rgb: {:?}", board.with_board(read_rgb));
+        thread::sleep(Duration::from_millis(250));
+    }
+}
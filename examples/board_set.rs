@@ -0,0 +1,34 @@
+use std::{thread, time::Duration};
+
+use firmata_client::{BoardSet, PinMode};
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+    let mut boards = BoardSet::new();
+    boards.connect("/dev/tty.usbmodem14201", 57_600, Duration::from_secs(5)).expect("left board connected");
+    boards.connect("/dev/tty.usbmodem14301", 57_600, Duration::from_secs(5)).expect("right board connected");
+	println!("setup complete");
+
+    boards.alias("led", ("/dev/tty.usbmodem14201", 13)).expect("led alias");
+    boards.alias("button", ("/dev/tty.usbmodem14301", 2)).expect("button alias");
+
+    boards.set_pin_mode("led", PinMode::Output).expect("pin mode set");
+    boards.set_pin_mode("button", PinMode::Pullup).expect("pin mode set");
+    boards.report_digital("button", true).expect("digital reporting mode");
+
+    loop {
+        let (messages, errors) = boards.poll();
+        for message in messages {
+            println!("{}: {:?}", message.board, message.message);
+        }
+        for error in errors {
+            eprintln!("{error}");
+        }
+
+        let pressed = boards.get_pin("button").expect("button pin").value == 0;
+        boards.digital_write("led", pressed).expect("digital write");
+
+        thread::sleep(Duration::from_millis(10));
+    }
+}
@@ -11,21 +11,15 @@ fn main() {
 		.stop_bits(StopBits::One)
 		.flow_control(FlowControl::None);
 
-    let mut board = firmata_client::Board::new(serial_port_builder);
-	
-	while !board.is_ready() {
-		board.poll().expect("successful polling");
-		println!("waiting...");
-        thread::sleep(Duration::from_millis(100));
-	}
+    let mut board = firmata_client::Board::connect(serial_port_builder, Duration::from_secs(5)).expect("board connected");
 	println!("setup complete");
 
     let led = 13;
     let button = 2;
 	
     board.report_digital(button, true).expect("digital reporting mode");
-    board.set_pin_mode(led, firmata_client::PIN_MODE_OUTPUT).expect("pin mode set");
-    board.set_pin_mode(button, firmata_client::PIN_MODE_INPUT | firmata_client::PIN_MODE_PULLUP).expect("pin mode set");
+    board.set_pin_mode(led, firmata_client::PinMode::Output).expect("pin mode set");
+    board.set_pin_mode(button, firmata_client::PinMode::Pullup).expect("pin mode set");
 
     println!("Starting loop...");
 
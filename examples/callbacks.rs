@@ -0,0 +1,38 @@
+use std::{thread, time::Duration};
+
+use serialport::*;
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+	let serial_port_builder = serialport::new("/dev/tty.usbmodem14201", 57_600)
+		.data_bits(DataBits::Eight)
+		.parity(Parity::None)
+		.stop_bits(StopBits::One)
+		.flow_control(FlowControl::None);
+
+    let mut board = firmata_client::Board::connect(serial_port_builder, Duration::from_secs(5)).expect("board connected");
+	println!("setup complete");
+
+    let button = 2;
+
+    board.set_pin_mode(button, firmata_client::PinMode::Pullup).expect("pin mode set");
+    board.report_digital(button, true).expect("digital reporting mode");
+    board.report_analog("A0", true).expect("analog reporting mode");
+
+    board.on_digital_change(button, |value| println!("button: {value}")).expect("digital callback");
+    let analog = board.on_analog("A0", |value| println!("A0: {value}")).expect("analog callback");
+
+    for _ in 0..1000 {
+        board.poll().expect("successful polling");
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    board.unsubscribe(analog);
+    board.report_analog("A0", false).expect("analog reporting mode");
+
+    loop {
+        board.poll().expect("successful polling");
+        thread::sleep(Duration::from_millis(10));
+    }
+}
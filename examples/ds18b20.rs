@@ -0,0 +1,35 @@
+use std::{thread, time::Duration};
+
+use serialport::*;
+
+use firmata_client::Ds18b20;
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+	let serial_port_builder = serialport::new("/dev/tty.usbmodem14201", 57_600)
+		.data_bits(DataBits::Eight)
+		.parity(Parity::None)
+		.stop_bits(StopBits::One)
+		.flow_control(FlowControl::None);
+
+    let mut board = firmata_client::Board::connect(serial_port_builder, Duration::from_secs(5)).expect("board connected");
+	println!("setup complete");
+
+    let pin = 4;
+    let timeout = Duration::from_secs(1);
+
+    board.onewire_config(pin, true).expect("onewire configured");
+    let sensors = Ds18b20::search(&mut board, pin, timeout).expect("search");
+    println!("found {} sensors", sensors.len());
+
+    loop {
+        for sensor in &sensors {
+            let celsius = sensor.read_celsius(&mut board, timeout).expect("temperature");
+            println!("{:016X}: {celsius}°C", sensor.address());
+        }
+
+        board.poll().expect("successful polling");
+        thread::sleep(Duration::from_millis(1000));
+    }
+}
@@ -0,0 +1,31 @@
+use std::{thread, time::Duration};
+
+use serialport::*;
+
+use firmata_client::Message;
+
+fn main() {
+	let serial_port_builder = serialport::new("/dev/tty.usbmodem14201", 57_600)
+		.data_bits(DataBits::Eight)
+		.parity(Parity::None)
+		.stop_bits(StopBits::One)
+		.flow_control(FlowControl::None);
+
+    let mut board = firmata_client::Board::connect(serial_port_builder, Duration::from_secs(5)).expect("board connected");
+	println!("setup complete");
+
+    let encoder = 0;
+
+    board.encoder_attach(encoder, 2, 3).expect("encoder attached");
+    board.encoder_report_auto(true).expect("automatic reporting");
+
+    loop {
+        for message in board.poll().expect("successful polling") {
+            if let Message::Encoder { id, position } = message {
+                println!("encoder {id} position: {position}");
+            }
+        }
+
+        thread::sleep(Duration::from_millis(10));
+    }
+}
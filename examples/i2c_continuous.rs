@@ -0,0 +1,40 @@
+use std::{thread, time::Duration};
+
+use serialport::*;
+
+const MPU6050: u8 = 0x68;
+const PWR_MGMT_1: u8 = 0x6B;
+const ACCEL_XOUT_H: u8 = 0x3B;
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+	let serial_port_builder = serialport::new("/dev/tty.usbmodem14201", 57_600)
+		.data_bits(DataBits::Eight)
+		.parity(Parity::None)
+		.stop_bits(StopBits::One)
+		.flow_control(FlowControl::None);
+
+    let mut board = firmata_client::Board::connect(serial_port_builder, Duration::from_secs(5)).expect("board connected");
+	println!("setup complete");
+
+    board.i2c_config(0).expect("i2c delay set");
+    board.i2c_write(MPU6050, &[PWR_MGMT_1, 0]).expect("sensor woken");
+
+    let acceleration = board.i2c_subscribe(u16::from(MPU6050), ACCEL_XOUT_H);
+    board.i2c_read_continuously(MPU6050, ACCEL_XOUT_H, 6).expect("continuous read");
+
+    loop {
+        board.poll().expect("successful polling");
+
+        for reply in acceleration.try_iter() {
+            let axes = reply.data
+                .chunks_exact(2)
+                .map(|pair| i16::from_be_bytes([pair[0], pair[1]]))
+                .collect::<Vec<i16>>();
+            println!("acceleration: {axes:?}");
+        }
+
+        thread::sleep(Duration::from_millis(10));
+    }
+}
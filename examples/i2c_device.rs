@@ -0,0 +1,39 @@
+use std::{thread, time::Duration};
+
+use firmata_client::{I2CBitfield, I2CDevice, I2CRegister};
+use serialport::*;
+
+const MPU6050: I2CDevice = I2CDevice::new(0x68);
+const WHO_AM_I: I2CRegister<u8> = I2CRegister::new(0x75);
+const PWR_MGMT_1: I2CRegister<u8> = I2CRegister::new(0x6B);
+const SLEEP: I2CBitfield<u8> = PWR_MGMT_1.bitfield(6, 1);
+const ACCEL_XOUT: I2CRegister<i16> = I2CRegister::new(0x3B);
+const TEMP_OUT: I2CRegister<i16> = I2CRegister::new(0x41);
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+	let serial_port_builder = serialport::new("/dev/tty.usbmodem14201", 57_600)
+		.data_bits(DataBits::Eight)
+		.parity(Parity::None)
+		.stop_bits(StopBits::One)
+		.flow_control(FlowControl::None);
+
+    let mut board = firmata_client::Board::connect(serial_port_builder, Duration::from_secs(5)).expect("board connected");
+	println!("setup complete");
+
+    board.i2c_config(0).expect("i2c delay set");
+
+    let id = MPU6050.read(&mut board, WHO_AM_I).expect("id read");
+    println!("device id: {id:#04X}");
+
+    MPU6050.write_field(&mut board, SLEEP, 0).expect("sensor woken");
+
+    loop {
+        let acceleration = MPU6050.read(&mut board, ACCEL_XOUT).expect("acceleration read");
+        let temperature = f32::from(MPU6050.read(&mut board, TEMP_OUT).expect("temperature read")) / 340.0 + 36.53;
+        println!("x acceleration: {acceleration}, temperature: {temperature:.1}");
+
+        thread::sleep(Duration::from_millis(500));
+    }
+}
@@ -0,0 +1,22 @@
+use std::time::Duration;
+
+use serialport::*;
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+	let serial_port_builder = serialport::new("/dev/tty.usbmodem14201", 57_600)
+		.data_bits(DataBits::Eight)
+		.parity(Parity::None)
+		.stop_bits(StopBits::One)
+		.flow_control(FlowControl::None);
+
+    let mut board = firmata_client::Board::connect(serial_port_builder, Duration::from_secs(5)).expect("board connected");
+	println!("setup complete");
+
+    board.i2c_config(0).expect("i2c delay set");
+
+    for address in board.i2c_scan(Duration::from_millis(50)).expect("bus scanned") {
+        println!("device found at {address:#04X}");
+    }
+}
@@ -9,18 +9,12 @@ fn main() {
 		.stop_bits(StopBits::One)
 		.flow_control(FlowControl::None);
 
-    let mut board = firmata_client::Board::new(serial_port_builder);
-
-	while !board.is_ready() {
-		board.poll().expect("successful polling");
-		println!("waiting...");
-        thread::sleep(Duration::from_millis(100));
-	}
+    let mut board = firmata_client::Board::connect(serial_port_builder, Duration::from_secs(5)).expect("board connected");
 	println!("setup complete");
 
     let pin = 5;
 
-    board.set_pin_mode(pin, firmata_client::PIN_MODE_PWM).expect("pin set");
+    board.set_pin_mode(pin, firmata_client::PinMode::Pwm).expect("pin set");
     board.analog_write(pin, 0).expect("pin write");
 
     println!("Starting loop...");
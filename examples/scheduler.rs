@@ -0,0 +1,41 @@
+use std::{thread, time::Duration};
+
+use serialport::*;
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+	let serial_port_builder = serialport::new("/dev/tty.usbmodem14201", 57_600)
+		.data_bits(DataBits::Eight)
+		.parity(Parity::None)
+		.stop_bits(StopBits::One)
+		.flow_control(FlowControl::None);
+
+    let mut board = firmata_client::Board::connect(serial_port_builder, Duration::from_secs(5)).expect("board connected");
+	println!("setup complete");
+
+    let pin = 13;
+    let task = 0;
+
+    board.set_pin_mode(pin, firmata_client::PinMode::Output).expect("pin mode set");
+
+    // a 50ms pulse every 500ms, timed by the board rather than over USB
+    let data = board.record_task(|board| {
+        board.digital_write(pin, true)?;
+        board.scheduler_delay_task(50)?;
+        board.digital_write(pin, false)?;
+        board.scheduler_schedule_task(task, 450)
+    }).expect("task recorded");
+
+    board.scheduler_create_task(task, &data).expect("task created");
+    board.scheduler_schedule_task(task, 0).expect("task scheduled");
+
+    let timeout = Duration::from_secs(1);
+    println!("tasks: {:?}", board.scheduler_query_all_tasks(timeout).expect("tasks"));
+    println!("task: {:?}", board.scheduler_query_task(task, timeout).expect("task"));
+
+    loop {
+        board.poll().expect("successful polling");
+        thread::sleep(Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,29 @@
+use std::io::{BufRead, BufReader};
+use std::time::Duration;
+
+use serialport::*;
+
+use firmata_client::SerialPortId;
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+	let serial_port_builder = serialport::new("/dev/tty.usbmodem14201", 57_600)
+		.data_bits(DataBits::Eight)
+		.parity(Parity::None)
+		.stop_bits(StopBits::One)
+		.flow_control(FlowControl::None);
+
+    let mut board = firmata_client::Board::connect(serial_port_builder, Duration::from_secs(5)).expect("board connected");
+	println!("setup complete");
+
+    // a GPS module on a software serial port, receiving on pin 10 and transmitting on pin 11
+    let mut gps = board
+        .serial_port(SerialPortId::Software(0), 9_600, Some(&10u8), Some(&11u8))
+        .expect("serial port opened");
+    gps.set_timeout(Duration::from_secs(5));
+
+    for line in BufReader::new(gps).lines() {
+        println!("{}", line.expect("nmea sentence"));
+    }
+}
@@ -2,6 +2,8 @@ use std::{thread, time::Duration};
 
 use serialport::*;
 
+use firmata_client::Servo;
+
 fn main() {
     tracing_subscriber::fmt::init();
 
@@ -11,18 +13,19 @@ fn main() {
 		.stop_bits(StopBits::One)
 		.flow_control(FlowControl::None);
 
-    let mut b = firmata_client::Board::new(serial_port_builder);
+    let mut b = firmata_client::Board::connect(serial_port_builder, Duration::from_secs(5)).expect("board connected");
+	println!("setup complete");
 
     let pin = 3;
 
-    b.set_pin_mode(pin, firmata_client::PIN_MODE_SERVO)
-        .expect("pin mode set");
+    let servo = Servo::attach(&mut b, pin, Servo::DEFAULT_MIN_PULSE, Servo::DEFAULT_MAX_PULSE)
+        .expect("servo attached");
 
     tracing::info!("Starting loop...");
 
     loop {
-        for value in 0..180 {
-            b.analog_write(pin, value).expect("analog write");
+        for value in 0..180u8 {
+            servo.write_degrees(&mut b, f32::from(value)).expect("servo write");
             tracing::info!("{}", value);
             thread::sleep(Duration::from_millis(10));
         }
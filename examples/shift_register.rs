@@ -0,0 +1,31 @@
+use std::{thread, time::Duration};
+
+use firmata_client::ShiftRegisterChain;
+use serialport::*;
+
+const DATA_PIN: u8 = 11;
+const CLOCK_PIN: u8 = 12;
+const LATCH_PIN: u8 = 8;
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+	let serial_port_builder = serialport::new("/dev/tty.usbmodem14201", 57_600)
+		.data_bits(DataBits::Eight)
+		.parity(Parity::None)
+		.stop_bits(StopBits::One)
+		.flow_control(FlowControl::None);
+
+    let mut board = firmata_client::Board::connect(serial_port_builder, Duration::from_secs(5)).expect("board connected");
+	println!("setup complete");
+
+    let mut chain = ShiftRegisterChain::new(&mut board, DATA_PIN, CLOCK_PIN, LATCH_PIN, 2).expect("chain set up");
+
+    loop {
+        for output in 0..chain.len() {
+            chain.digital_write(&mut board, output, true).expect("output on");
+            thread::sleep(Duration::from_millis(100));
+            chain.digital_write(&mut board, output, false).expect("output off");
+        }
+    }
+}
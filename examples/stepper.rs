@@ -0,0 +1,38 @@
+use std::{thread, time::Duration};
+
+use serialport::*;
+
+use firmata_client::{Message, Stepper, StepperConfig, StepperInterface};
+
+fn main() {
+    tracing_subscriber::fmt::init();
+
+	let serial_port_builder = serialport::new("/dev/tty.usbmodem14201", 57_600)
+		.data_bits(DataBits::Eight)
+		.parity(Parity::None)
+		.stop_bits(StopBits::One)
+		.flow_control(FlowControl::None);
+
+    let mut board = firmata_client::Board::connect(serial_port_builder, Duration::from_secs(5)).expect("board connected");
+	println!("setup complete");
+
+    let config = StepperConfig::new(StepperInterface::Driver { step_pin: 2, direction_pin: 3 });
+    let stepper = Stepper::attach(&mut board, 0, &config).expect("stepper attached");
+    stepper.set_speed(&mut board, 400.0).expect("speed set");
+    stepper.set_acceleration(&mut board, 200.0).expect("acceleration set");
+
+    let mut target = 2000;
+    stepper.move_to(&mut board, target).expect("move");
+
+    loop {
+        for message in board.poll().expect("successful polling") {
+            if let Message::StepperMoveComplete { position, .. } = message {
+                println!("arrived at {position}");
+                target = -target;
+                stepper.move_to(&mut board, target).expect("move");
+            }
+        }
+
+        thread::sleep(Duration::from_millis(10));
+    }
+}
@@ -0,0 +1,351 @@
+//! A [`Board`] for tokio, driven by an async serial port or TCP stream.
+//!
+//! The board's state is kept by a background task that reads the transport, so queries resolve as
+//! soon as their reply arrives and every decoded message is also available as a `Stream`.
+
+use std::sync::{
+	Arc,
+	Mutex,
+	MutexGuard,
+	PoisonError
+};
+use std::sync::mpsc::{
+	Receiver,
+	Sender
+};
+use std::time::Duration;
+
+use tokio::io::{
+	AsyncRead,
+	AsyncReadExt,
+	AsyncWrite,
+	AsyncWriteExt,
+	ReadHalf
+};
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{
+	Stream,
+	StreamExt
+};
+
+use crate::connection_wrapper::ConnectionWrapper;
+use crate::types::{
+	Error,
+	I2CReply,
+	I2CRequest,
+	Message,
+	Pin,
+	PinRef,
+	Result,
+};
+use crate::Board;
+
+type Writer = Box<dyn AsyncWrite + Send + Unpin>;
+
+/// The board along with the ends of its detached connection.
+struct Shared {
+	board: Board,
+	incoming: Sender<Vec<u8>>,
+	outgoing: Receiver<Vec<u8>>,
+}
+
+impl Shared {
+	/// Take everything the board has written since the last call.
+	fn take_outgoing(&self) -> Vec<u8> {
+		self.outgoing.try_iter().flatten().collect()
+	}
+}
+
+fn lock(shared: &Mutex<Shared>) -> MutexGuard<'_, Shared> {
+	shared.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The task driving the transport, stopped when dropped so that the transport is closed with it.
+struct Driver(tokio::task::JoinHandle<()>);
+
+impl Drop for Driver {
+	fn drop(&mut self) {
+		self.0.abort();
+	}
+}
+
+/// A Firmata board driven by tokio.
+///
+/// Cloning the board gives another handle to the same connection, which is closed once every handle
+/// has been dropped.
+pub struct AsyncBoard {
+	shared: Arc<Mutex<Shared>>,
+	writer: Arc<tokio::sync::Mutex<Writer>>,
+	messages: broadcast::Receiver<Message>,
+	driver: Arc<Driver>,
+}
+
+impl Clone for AsyncBoard {
+	fn clone(&self) -> Self {
+		Self {
+			shared: Arc::clone(&self.shared),
+			writer: Arc::clone(&self.writer),
+			messages: self.messages.resubscribe(),
+			driver: Arc::clone(&self.driver),
+		}
+	}
+}
+
+// creation
+	impl AsyncBoard {
+		/// Number of messages held for a slow `messages` stream before the oldest are skipped.
+		pub const MESSAGE_CAPACITY: usize = 256;
+
+		/// Drive a board over `transport`, without waiting for the handshake. This spawns the task that
+		/// reads the transport, so it must be called from within a tokio runtime.
+		pub fn new<T>(transport: T) -> AsyncBoard
+		where
+			T: AsyncRead + AsyncWrite + Send + 'static,
+		{
+			let (reader, writer) = tokio::io::split(transport);
+			let (connection_wrapper, incoming, outgoing) = ConnectionWrapper::detached();
+
+			let shared = Arc::new(Mutex::new(Shared { board: Board::with_connection(connection_wrapper), incoming, outgoing }));
+			let writer: Arc<tokio::sync::Mutex<Writer>> = Arc::new(tokio::sync::Mutex::new(Box::new(writer)));
+			let (sender, messages) = broadcast::channel(Self::MESSAGE_CAPACITY);
+
+			let driver = Arc::new(Driver(tokio::spawn(drive(reader, Arc::clone(&shared), Arc::clone(&writer), sender))));
+
+			AsyncBoard { shared, writer, messages, driver }
+		}
+
+		/// Drive a board over `transport` and wait up to `timeout` for the firmware, capability and
+		/// analog mapping handshake to finish.
+		pub async fn connect<T>(transport: T, timeout: Duration) -> Result<AsyncBoard>
+		where
+			T: AsyncRead + AsyncWrite + Send + 'static,
+		{
+			let board = AsyncBoard::new(transport);
+			let mut messages = board.messages.resubscribe();
+
+			board.with_board(Board::report_firmware).await?;
+
+			let handshake = async {
+				while !lock(&board.shared).board.is_ready() {
+					match messages.recv().await {
+						Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {},
+						Err(broadcast::error::RecvError::Closed) => return Err(Error::Disconnected),
+					}
+				}
+				Ok(())
+			};
+
+			match tokio::time::timeout(timeout, handshake).await {
+				Ok(result) => result.map(|()| board),
+				Err(_) => Err(Error::HandshakeTimeout { stage: lock(&board.shared).board.handshake_stage() }),
+			}
+		}
+
+		/// Open the serial port described by `serial_port_builder` and connect to the board on it.
+		pub async fn connect_serial(serial_port_builder: serialport::SerialPortBuilder, timeout: Duration) -> Result<AsyncBoard> {
+			let port = tokio_serial::SerialStream::open(&serial_port_builder)?;
+			AsyncBoard::connect(port, timeout).await
+		}
+
+		/// Open a TCP connection to a board at `address`, such as one running a Firmata WiFi or Ethernet
+		/// sketch, and connect to it. The `timeout` covers the handshake only.
+		pub async fn connect_tcp(address: impl tokio::net::ToSocketAddrs, timeout: Duration) -> Result<AsyncBoard> {
+			let stream = tokio::net::TcpStream::connect(address).await?;
+			stream.set_nodelay(true)?;
+			AsyncBoard::connect(stream, timeout).await
+		}
+	}
+
+// access
+	impl AsyncBoard {
+		/// Run `f` against the board and send whatever it writes.
+		///
+		/// Any of the board's methods that only write can be used this way, but those that block waiting
+		/// for a reply can not, as replies only arrive through the async board.
+		pub async fn with_board<T>(&self, f: impl FnOnce(&mut Board) -> Result<T>) -> Result<T> {
+			let mut writer = self.writer.lock().await;
+
+			let (result, outgoing) = {
+				let mut shared = lock(&self.shared);
+				let result = f(&mut shared.board);
+				(result, shared.take_outgoing())
+			};
+
+			if !outgoing.is_empty() {
+				writer.write_all(&outgoing).await?;
+				writer.flush().await?;
+			}
+
+			result
+		}
+
+		/// Get every message decoded from the board from now on. A stream that falls more than
+		/// `MESSAGE_CAPACITY` messages behind skips the oldest.
+		pub fn messages(&self) -> impl Stream<Item = Message> + Send + 'static {
+			BroadcastStream::new(self.messages.resubscribe()).filter_map(std::result::Result::ok)
+		}
+
+		/// Send with `send`, then wait up to `timeout` for `matcher` to pick a result out of one of the
+		/// messages that follow.
+		async fn request<T>(
+			&self,
+			timeout: Duration,
+			source: &str,
+			send: impl FnOnce(&mut Board) -> Result<()>,
+			mut matcher: impl FnMut(&mut Board, &Message) -> Option<T>,
+		) -> Result<T> {
+			let mut messages = self.messages.resubscribe();
+
+			self.with_board(send).await?;
+
+			let reply = async {
+				loop {
+					match messages.recv().await {
+						Ok(message) => {
+							let result = matcher(&mut lock(&self.shared).board, &message);
+							if let Some(result) = result {
+								return Ok(result);
+							}
+						},
+						Err(broadcast::error::RecvError::Lagged(_)) => {},
+						Err(broadcast::error::RecvError::Closed) => return Err(Error::Disconnected),
+					}
+				}
+			};
+
+			tokio::time::timeout(timeout, reply)
+				.await
+				.unwrap_or_else(|_| Err(Error::Timeout { source: source.to_string() }))
+		}
+	}
+
+// queries
+	impl AsyncBoard {
+		/// Ask the board for its firmware, returning the firmware name and version.
+		pub async fn firmware(&self, timeout: Duration) -> Result<(String, String)> {
+			self.request(timeout, "firmware", Board::report_firmware, |board, message| match message {
+				Message::ReportFirmwareName(name) => Some((name.clone(), board.get_firmware_version().cloned().unwrap_or_default())),
+				_ => None,
+			}).await
+		}
+
+		/// Ask the board for the mode and value of `pin`, returning the updated pin.
+		pub async fn query_pin_state(&self, pin: impl PinRef, timeout: Duration) -> Result<Pin> {
+			let pin = pin.resolve(&lock(&self.shared).board)?;
+
+			self.request(timeout, "query_pin_state", |board| board.query_pin_state(pin), |board, message| match message {
+				Message::PinStateResponse { pin: Some(reply_pin) } if *reply_pin == pin => board.get_pin(pin as usize).cloned(),
+				_ => None,
+			}).await
+		}
+
+		/// Send a reading I2C `request` and wait up to `timeout` for its reply.
+		pub async fn i2c_read_request(&self, request: &I2CRequest, timeout: Duration) -> Result<I2CReply> {
+			let waiter = lock(&self.shared).board.await_i2c_reply(request.get_address(), request.get_register());
+
+			let result = self.request(timeout, "i2c_read_request", |board| board.i2c_request(request), |board, message| {
+				if *message != Message::I2CReply {
+					return None;
+				}
+
+				board.take_awaited_i2c_reply(waiter)
+			}).await;
+
+			lock(&self.shared).board.cancel_i2c_wait(waiter);
+			result
+		}
+
+		/// Read `size` bytes starting at `register` from the I2C device at the specified `address`, and wait
+		/// up to `timeout` for the reply.
+		pub async fn i2c_read_register(&self, address: u8, register: u8, size: u8, timeout: Duration) -> Result<I2CReply> {
+			self.i2c_read_request(&I2CRequest::read(u16::from(address), size).register(register), timeout).await
+		}
+	}
+
+/// Read the transport, decode what arrives, send the board's responses to it, and publish the
+/// decoded messages. Runs until the transport closes or the last `AsyncBoard` is dropped.
+async fn drive<R>(
+	mut reader: ReadHalf<R>,
+	shared: Arc<Mutex<Shared>>,
+	writer: Arc<tokio::sync::Mutex<Writer>>,
+	sender: broadcast::Sender<Message>,
+)
+where
+	R: AsyncRead + Send,
+{
+	let mut buf = vec![0; 1024];
+
+	loop {
+		let len = match reader.read(&mut buf).await {
+			Ok(0) => break,
+			Ok(len) => len,
+			Err(error) => {
+				tracing::warn!("read error: {error}");
+				break;
+			},
+		};
+
+		let mut writer = writer.lock().await;
+
+		// the board answers the firmware report with its initial queries, which must go out too
+			let (messages, outgoing) = {
+				let mut shared = lock(&shared);
+				if shared.incoming.send(buf[..len].to_vec()).is_err() {
+					break;
+				}
+				let messages = shared.board.poll();
+				(messages, shared.take_outgoing())
+			};
+
+		if !outgoing.is_empty() {
+			if let Err(error) = writer.write_all(&outgoing).await {
+				tracing::warn!("write error: {error}");
+				break;
+			}
+			if let Err(error) = writer.flush().await {
+				tracing::warn!("flush error: {error}");
+				break;
+			}
+		}
+		drop(writer);
+
+		match messages {
+			Ok(messages) => {
+				for message in messages {
+					// there being no one listening is not an error
+						let _ = sender.send(message);
+				}
+			},
+			Err(error) => tracing::warn!("poll error: {error}"),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use tokio::io::AsyncReadExt;
+
+	use super::AsyncBoard;
+
+	#[test]
+	fn dropping_the_last_handle_closes_the_transport() {
+		let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+
+		runtime.block_on(async {
+			let (transport, mut peer) = tokio::io::duplex(64);
+			let board = AsyncBoard::new(transport);
+			let clone = board.clone();
+
+			drop(board);
+			tokio::task::yield_now().await;
+			assert!(tokio::time::timeout(Duration::from_millis(50), peer.read(&mut [0; 1])).await.is_err());
+
+			drop(clone);
+			let read = tokio::time::timeout(Duration::from_secs(1), peer.read(&mut [0; 1])).await;
+			assert_eq!(read.unwrap().unwrap(), 0);
+		});
+	}
+}
@@ -0,0 +1,125 @@
+use crate::types::{
+    I2CReply,
+    Message,
+    PinRef,
+    Result,
+};
+
+use super::Board;
+
+/// Identifies a callback registered on a [`Board`], for removing it with [`Board::unsubscribe`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CallbackHandle(u64);
+
+enum Callback {
+	DigitalChange { pin: u8, callback: Box<dyn FnMut(bool) + Send> },
+	Analog { pin: u8, callback: Box<dyn FnMut(u16) + Send> },
+	I2C { address: u16, callback: Box<dyn FnMut(&I2CReply) + Send> },
+	Message(Box<dyn FnMut(&Message) + Send>),
+}
+
+/// The callbacks registered on a board, in the order they were added.
+#[derive(Default)]
+pub(super) struct Callbacks {
+	next_id: u64,
+	callbacks: Vec<(CallbackHandle, Callback)>,
+}
+
+impl Callbacks {
+	fn add(&mut self, callback: Callback) -> CallbackHandle {
+		let handle = CallbackHandle(self.next_id);
+		self.next_id += 1;
+		self.callbacks.push((handle, callback));
+		handle
+	}
+
+	fn remove(&mut self, handle: CallbackHandle) -> bool {
+		let len = self.callbacks.len();
+		self.callbacks.retain(|(other, _)| *other != handle);
+		self.callbacks.len() != len
+	}
+
+	pub(super) fn digital_change(&mut self, pin: u8, value: bool) {
+		for (_, callback) in &mut self.callbacks {
+			if let Callback::DigitalChange { pin: callback_pin, callback } = callback {
+				if *callback_pin == pin {
+					callback(value);
+				}
+			}
+		}
+	}
+
+	pub(super) fn analog(&mut self, pin: u8, value: u16) {
+		for (_, callback) in &mut self.callbacks {
+			if let Callback::Analog { pin: callback_pin, callback } = callback {
+				if *callback_pin == pin {
+					callback(value);
+				}
+			}
+		}
+	}
+
+	pub(super) fn i2c(&mut self, reply: &I2CReply) {
+		for (_, callback) in &mut self.callbacks {
+			if let Callback::I2C { address, callback } = callback {
+				if *address == reply.address {
+					callback(reply);
+				}
+			}
+		}
+	}
+
+	pub(super) fn message(&mut self, message: &Message) {
+		for (_, callback) in &mut self.callbacks {
+			if let Callback::Message(callback) = callback {
+				callback(message);
+			}
+		}
+	}
+}
+
+impl std::fmt::Debug for Callbacks {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("Callbacks")
+			.field("handles", &self.callbacks.iter().map(|(handle, _)| handle).collect::<Vec<_>>())
+			.finish()
+	}
+}
+
+// callbacks
+	impl Board {
+		/// Call `callback` with the new value of `pin` whenever the board reports that it changed. The pin
+		/// must be an input with digital reporting enabled for its port.
+		#[tracing::instrument(skip(self, callback), err, ret, level = "DEBUG")]
+		pub fn on_digital_change(&mut self, pin: impl PinRef, callback: impl FnMut(bool) + Send + 'static) -> Result<CallbackHandle> {
+			let pin = pin.resolve(self)?;
+			Ok(self.callbacks.add(Callback::DigitalChange { pin, callback: Box::new(callback) }))
+		}
+
+		/// Call `callback` with the value of `pin` every time the board reports it. The pin must have
+		/// analog reporting enabled.
+		#[tracing::instrument(skip(self, callback), err, ret, level = "DEBUG")]
+		pub fn on_analog(&mut self, pin: impl PinRef, callback: impl FnMut(u16) + Send + 'static) -> Result<CallbackHandle> {
+			let pin = pin.resolve(self)?;
+			Ok(self.callbacks.add(Callback::Analog { pin, callback: Box::new(callback) }))
+		}
+
+		/// Call `callback` with every reply from the I2C device at `address`. Unlike
+		/// [`Board::i2c_subscribe`], this leaves the replies in the board's I2C data.
+		#[tracing::instrument(skip(self, callback), ret, level = "DEBUG")]
+		pub fn on_i2c(&mut self, address: u16, callback: impl FnMut(&I2CReply) + Send + 'static) -> CallbackHandle {
+			self.callbacks.add(Callback::I2C { address, callback: Box::new(callback) })
+		}
+
+		/// Call `callback` with every message as it is decoded, before it is returned from `poll`.
+		#[tracing::instrument(skip(self, callback), ret, level = "DEBUG")]
+		pub fn on_message(&mut self, callback: impl FnMut(&Message) + Send + 'static) -> CallbackHandle {
+			self.callbacks.add(Callback::Message(Box::new(callback)))
+		}
+
+		/// Remove the callback registered as `handle`, returning whether it was still registered.
+		#[tracing::instrument(skip(self), ret, level = "DEBUG")]
+		pub fn unsubscribe(&mut self, handle: CallbackHandle) -> bool {
+			self.callbacks.remove(handle)
+		}
+	}
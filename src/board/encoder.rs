@@ -0,0 +1,80 @@
+use crate::constants::{
+    ENCODER_ATTACH,
+    ENCODER_DATA,
+    ENCODER_DETACH,
+    ENCODER_REPORT_AUTO,
+    ENCODER_REPORT_POSITION,
+    ENCODER_REPORT_POSITIONS,
+    ENCODER_RESET_POSITION,
+    END_SYSEX,
+    START_SYSEX
+};
+use crate::types::{
+    Error,
+    PinMode,
+    PinRef,
+    Result,
+};
+
+use super::Board;
+
+// encoder
+	impl Board {
+		/// Attach quadrature encoder `id` to the pin pair `pin_a` and `pin_b`, placing both pins in
+		/// `PinMode::Encoder`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn encoder_attach(&mut self, id: u8, pin_a: impl PinRef, pin_b: impl PinRef) -> Result<()> {
+			let pin_a = pin_a.resolve(self)?;
+			let pin_b = pin_b.resolve(self)?;
+
+			for pin in [pin_a, pin_b] {
+				let Some(p) = self.pins.get(pin as usize) else {
+					return Err(Error::PinOutOfBounds { pin, len: self.pins.len(), source: "encoder_attach".to_string() })
+				};
+				if !p.modes.contains(&PinMode::Encoder) {
+					return Err(Error::InvalidPinMode { pin, mode: PinMode::Encoder, modes: p.modes.clone() });
+				}
+			}
+			for pin in [pin_a, pin_b] {
+				self.pins[pin as usize].set_mode(PinMode::Encoder);
+			}
+
+			self.encoder_positions.insert(id, 0);
+
+			self.write_to_connection(&[START_SYSEX, ENCODER_DATA, ENCODER_ATTACH, id, pin_a, pin_b, END_SYSEX])
+		}
+
+		/// Detach encoder `id`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn encoder_detach(&mut self, id: u8) -> Result<()> {
+			self.encoder_positions.remove(&id);
+			self.write_to_connection(&[START_SYSEX, ENCODER_DATA, ENCODER_DETACH, id, END_SYSEX])
+		}
+
+		/// Ask for the position of encoder `id`, which arrives as a `Message::Encoder`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn encoder_report_position(&mut self, id: u8) -> Result<()> {
+			self.write_to_connection(&[START_SYSEX, ENCODER_DATA, ENCODER_REPORT_POSITION, id, END_SYSEX])
+		}
+
+		/// Ask for the positions of all attached encoders.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn encoder_report_positions(&mut self) -> Result<()> {
+			self.write_to_connection(&[START_SYSEX, ENCODER_DATA, ENCODER_REPORT_POSITIONS, END_SYSEX])
+		}
+
+		/// Reset the position of encoder `id` to zero.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn encoder_reset_position(&mut self, id: u8) -> Result<()> {
+			if let Some(position) = self.encoder_positions.get_mut(&id) {
+				*position = 0;
+			}
+			self.write_to_connection(&[START_SYSEX, ENCODER_DATA, ENCODER_RESET_POSITION, id, END_SYSEX])
+		}
+
+		/// Set whether the board reports the positions of all encoders on every sampling interval.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn encoder_report_auto(&mut self, state: bool) -> Result<()> {
+			self.write_to_connection(&[START_SYSEX, ENCODER_DATA, ENCODER_REPORT_AUTO, u8::from(state), END_SYSEX])
+		}
+	}
@@ -0,0 +1,451 @@
+#![allow(clippy::cast_possible_truncation)]
+
+use std::collections::VecDeque;
+use std::sync::mpsc::Receiver;
+use std::time::Duration;
+
+use crate::constants::{
+    I2C_SCAN_FIRST_ADDRESS,
+    I2C_SCAN_LAST_ADDRESS
+};
+use crate::types::{
+    Error,
+    I2CBitfield,
+    I2CConfig,
+    I2CRegister,
+    I2CReply,
+    I2CRequest,
+    Message,
+    PinMode,
+    RegisterValue,
+    Result,
+};
+
+use super::Board;
+
+/// The I2C replies stored for one device.
+#[derive(Debug, Default)]
+pub(super) struct I2CReplies {
+	pub(super) replies: VecDeque<I2CReply>,
+}
+
+impl I2CReplies {
+	/// Keep `reply` for later, dropping the oldest reply once over `limit`.
+	pub(super) fn push(&mut self, reply: I2CReply, limit: usize) {
+		if self.replies.len() >= limit {
+			self.replies.pop_front();
+		}
+		self.replies.push_back(reply);
+	}
+}
+
+/// A blocking I2C read waiting on its reply, which it takes as the reply is decoded so that neither the
+/// storage limit nor other replies can push it out first.
+///
+/// A read sent without a register waits on the address alone, as the firmware fills in a dummy
+/// register of 0 for its reply.
+#[derive(Debug)]
+pub(super) struct I2CWaiter {
+	id: u64,
+	address: u16,
+	register: Option<u8>,
+	reply: Option<I2CReply>,
+}
+
+impl I2CWaiter {
+	/// Take `reply` if this waiter is still waiting on it, otherwise hand it back.
+	pub(super) fn offer(&mut self, reply: I2CReply) -> Option<I2CReply> {
+		if self.reply.is_some() || self.address != reply.address || self.register.is_some_and(|register| register != reply.register) {
+			return Some(reply);
+		}
+		self.reply = Some(reply);
+		None
+	}
+}
+
+// storage
+	impl Board {
+		/// Default number of I2C replies kept for each device.
+		pub const DEFAULT_I2C_DATA_LIMIT: usize = 32;
+
+		/// Set the number of I2C replies kept for each device, dropping the oldest beyond it. At least one
+		/// reply is always kept, so a `limit` of 0 is treated as 1.
+		pub fn set_i2c_data_limit(&mut self, limit: usize) {
+			let limit = limit.max(1);
+			self.i2c_data_limit = limit;
+			for replies in self.i2c_data.values_mut() {
+				let excess = replies.replies.len().saturating_sub(limit);
+				replies.replies.drain(..excess);
+			}
+		}
+
+		/// Remove and return the oldest stored I2C reply from the device at `address`.
+		pub fn take_i2c_reply(&mut self, address: u16) -> Option<I2CReply> {
+			self.i2c_data.get_mut(&address)?.replies.pop_front()
+		}
+
+		/// Remove and return every stored I2C reply from the device at `address`, oldest first.
+		pub fn drain_i2c_data(&mut self, address: u16) -> Vec<I2CReply> {
+			self.i2c_data
+				.get_mut(&address)
+				.map(|replies| replies.replies.drain(..).collect())
+				.unwrap_or_default()
+		}
+
+		/// Start waiting on the next reply from `register` of the device at `address`, or from any register
+		/// when `register` is `None`, returning the id to collect it with.
+		pub(crate) fn await_i2c_reply(&mut self, address: u16, register: Option<u8>) -> u64 {
+			let id = self.next_i2c_waiter;
+			self.next_i2c_waiter += 1;
+			self.i2c_waiters.push(I2CWaiter { id, address, register, reply: None });
+			id
+		}
+
+		/// Take the reply for the waiter `id` once it has arrived, which ends the wait.
+		pub(crate) fn take_awaited_i2c_reply(&mut self, id: u64) -> Option<I2CReply> {
+			let index = self.i2c_waiters.iter().position(|waiter| waiter.id == id && waiter.reply.is_some())?;
+			self.i2c_waiters.remove(index).reply
+		}
+
+		/// Stop the waiter `id`, storing any reply it took that was not collected.
+		pub(crate) fn cancel_i2c_wait(&mut self, id: u64) {
+			let Some(index) = self.i2c_waiters.iter().position(|waiter| waiter.id == id) else { return; };
+			if let Some(reply) = self.i2c_waiters.remove(index).reply {
+				self.i2c_data.entry(reply.address).or_default().push(reply, self.i2c_data_limit);
+			}
+		}
+
+		/// Remove and return every stored I2C reply, grouped by device and oldest first within each.
+		pub fn drain_all_i2c_data(&mut self) -> Vec<I2CReply> {
+			let mut addresses = self.i2c_data.keys().copied().collect::<Vec<u16>>();
+			addresses.sort_unstable();
+
+			addresses.into_iter().flat_map(|address| self.drain_i2c_data(address)).collect()
+		}
+	}
+
+// i2c
+	impl Board {
+		/// Configure the `delay` in microseconds for I2C devices that require a delay between when the
+		/// register is written to and the data in that register can be read.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn i2c_config(&mut self, delay: u16) -> Result<()> {
+			self.i2c_configure(&I2CConfig::new().delay(delay))
+		}
+
+		/// Enable I2C with the settings in `config`. The pins the board reports as supporting I2C are
+		/// placed in `PinMode::I2C`, as the firmware claims them for the bus. Before the board has reported
+		/// its capabilities the settings are sent without any checks, as there is nothing to check against.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn i2c_configure(&mut self, config: &I2CConfig) -> Result<()> {
+			if self.pins.is_empty() {
+				return self.write_to_connection(&config.to_sysex());
+			}
+
+			let mut i2c_pins = self.pins
+				.iter_mut()
+				.filter(|pin| pin.modes.contains(&PinMode::I2C))
+				.peekable();
+
+			if i2c_pins.peek().is_none() {
+				return Err(Error::UnsupportedFeature { feature: "i2c".to_string() });
+			}
+			for pin in i2c_pins {
+				pin.set_mode(PinMode::I2C);
+			}
+
+			self.write_to_connection(&config.to_sysex())
+		}
+
+		/// Send an I2C `request`. Any data it reads arrives as a `Message::I2CReply`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn i2c_request(&mut self, request: &I2CRequest) -> Result<()> {
+			self.write_to_connection(&request.to_sysex())
+		}
+
+		/// Send a reading I2C `request` and wait up to `timeout` for its reply.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn i2c_read_request(&mut self, request: &I2CRequest, timeout: Duration) -> Result<I2CReply> {
+			let waiter = self.await_i2c_reply(request.get_address(), request.get_register());
+
+			let result = self.i2c_request(request).and_then(|()| {
+				self.poll_until(timeout, "i2c_read_request", |board, message| {
+					if *message != Message::I2CReply {
+						return None;
+					}
+
+					board.take_awaited_i2c_reply(waiter)
+				})
+			});
+
+			self.cancel_i2c_wait(waiter);
+			result
+		}
+
+    	/// Read `size` bytes from I2C device at the specified `address`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn i2c_read(&mut self, address: u8, size: u8) -> Result<()> {
+			self.i2c_request(&I2CRequest::read(u16::from(address), size))
+		}
+
+		/// Read `size` bytes starting at `register` from the I2C device at the specified `address`, and wait
+		/// up to `timeout` for the reply.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn i2c_read_register(&mut self, address: u8, register: u8, size: u8, timeout: Duration) -> Result<I2CReply> {
+			self.i2c_read_request(&I2CRequest::read(u16::from(address), size).register(register), timeout)
+		}
+
+		/// Have the board read `size` bytes starting at `register` from the I2C device at the specified
+		/// `address` on every sampling interval, until stopped with [`Board::i2c_stop_reading`].
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn i2c_read_continuously(&mut self, address: u8, register: u8, size: u8) -> Result<()> {
+			self.i2c_request(&I2CRequest::read_continuously(u16::from(address), size).register(register))
+		}
+
+		/// Stop all continuous reads from the I2C device at the specified `address`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn i2c_stop_reading(&mut self, address: u8) -> Result<()> {
+			self.i2c_request(&I2CRequest::stop_reading(u16::from(address)))
+		}
+
+		/// Subscribe to the replies from `register` of the I2C device at `address`. Replies delivered to a
+		/// subscriber are not kept in the board's I2C data, though blocking reads of the same register still
+		/// receive theirs. Dropping the receiver ends the subscription.
+		pub fn i2c_subscribe(&mut self, address: u16, register: u8) -> Receiver<I2CReply> {
+			let (sender, receiver) = std::sync::mpsc::channel();
+			self.i2c_subscriptions.push((address, register, sender));
+			receiver
+		}
+
+		/// Probe every 7-bit address outside the reserved ranges and return those where a device responds.
+		/// The firmware answers every probe, with no data where no device responds, so a probe that gets
+		/// no answer within `timeout` fails the scan.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn i2c_scan(&mut self, timeout: Duration) -> Result<Vec<u8>> {
+			let mut addresses = vec![];
+
+			for address in I2C_SCAN_FIRST_ADDRESS..=I2C_SCAN_LAST_ADDRESS {
+				let reply = self.i2c_read_request(&I2CRequest::read(u16::from(address), 1), timeout)?;
+				if !reply.data.is_empty() {
+					addresses.push(address);
+				}
+			}
+
+			Ok(addresses)
+		}
+
+    	/// Write `data` to the I2C device at the specified `address`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn i2c_write(&mut self, address: u8, data: &[u8]) -> Result<()> {
+			self.i2c_request(&I2CRequest::write(u16::from(address), data))
+		}
+	}
+
+/// An I2C device, whose registers are read and written as typed values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct I2CDevice {
+	address: u16,
+	timeout: Duration,
+}
+
+// creation
+	impl I2CDevice {
+		/// Default time a read waits for the device to reply.
+		pub const DEFAULT_TIMEOUT: Duration = Duration::from_millis(100);
+
+		#[must_use]
+		pub const fn new(address: u16) -> Self {
+			Self { address, timeout: Self::DEFAULT_TIMEOUT }
+		}
+
+		/// Wait up to `timeout` for the device to reply to reads.
+		#[must_use]
+		pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+			self.timeout = timeout;
+			self
+		}
+	}
+
+// get
+	impl I2CDevice {
+		#[must_use]
+		/// Get the address of the device.
+		pub fn address(&self) -> u16 {
+			self.address
+		}
+
+		#[must_use]
+		/// Get the time a read waits for the device to reply.
+		pub fn timeout(&self) -> Duration {
+			self.timeout
+		}
+	}
+
+// registers
+	impl I2CDevice {
+		/// Read `len` bytes starting at `register`.
+		pub fn read_bytes(&self, board: &mut Board, register: u8, len: u8) -> Result<Vec<u8>> {
+			let reply = board.i2c_read_request(&I2CRequest::read(self.address, len).register(register), self.timeout)?;
+
+			if reply.data.len() < usize::from(len) {
+				return Err(Error::I2CShortRead { address: self.address, expected: usize::from(len), received: reply.data.len() });
+			}
+
+			Ok(reply.data)
+		}
+
+		/// Write `data` starting at `register`.
+		pub fn write_bytes(&self, board: &mut Board, register: u8, data: &[u8]) -> Result<()> {
+			board.i2c_request(&I2CRequest::write(self.address, data).register(register))
+		}
+
+		/// Read the value of `register`.
+		pub fn read<T: RegisterValue>(&self, board: &mut Board, register: I2CRegister<T>) -> Result<T> {
+			let data = self.read_bytes(board, register.get_address(), T::WIDTH as u8)?;
+			Ok(T::from_bytes(&data, register.get_endianness()))
+		}
+
+		/// Write `value` to `register`.
+		pub fn write<T: RegisterValue>(&self, board: &mut Board, register: I2CRegister<T>, value: T) -> Result<()> {
+			self.write_bytes(board, register.get_address(), &value.to_bytes(register.get_endianness()))
+		}
+
+		/// Read the value of `field`.
+		pub fn read_field<T: RegisterValue>(&self, board: &mut Board, field: I2CBitfield<T>) -> Result<u32> {
+			Ok(field.extract(self.read(board, field.get_register())?))
+		}
+
+		/// Write `value` to `field`, leaving the rest of its register untouched.
+		pub fn write_field<T: RegisterValue>(&self, board: &mut Board, field: I2CBitfield<T>, value: u32) -> Result<()> {
+			let register = field.get_register();
+			let current = self.read(board, register)?;
+			self.write(board, register, field.insert(current, value))
+		}
+	}
+
+#[cfg(test)]
+pub(crate) mod tests {
+	use std::collections::HashMap;
+	use std::time::Duration;
+
+	use crate::constants::{
+		END_SYSEX,
+		I2C_REPLY,
+		I2C_REQUEST,
+		START_SYSEX,
+		STRING_DATA
+	};
+	use crate::types::I2CRequest;
+
+	use super::Board;
+
+	const TIMEOUT: Duration = Duration::from_secs(1);
+
+	fn sysex(command: u8, data: &[u8]) -> Vec<u8> {
+		let mut buf = vec![START_SYSEX, command];
+		for datum in data {
+			buf.push(datum & 0x7F);
+			buf.push(datum >> 7);
+		}
+		buf.push(END_SYSEX);
+		buf
+	}
+
+	/// A board whose I2C requests are answered the way StandardFirmata's `readAndReportData` does, by
+	/// the devices in `devices`, each a memory read from and written to at a register pointer.
+	pub(crate) fn board_with_devices(devices: &[(u16, &[u8])]) -> Board {
+		let (board, incoming, outgoing) = Board::detached();
+		let mut devices = devices.iter().map(|(address, memory)| (*address, (memory.to_vec(), 0usize))).collect::<HashMap<_, _>>();
+
+		std::thread::spawn(move || {
+			for request in outgoing {
+				let [START_SYSEX, I2C_REQUEST, address, mode, ref args @ .., END_SYSEX] = request[..] else { continue; };
+				let address = if mode & 0x20 == 0 { u16::from(address) } else { u16::from(address) | u16::from(mode & 0x07) << 7 };
+				let args = args.chunks_exact(2).map(|pair| pair[0] | pair[1] << 7).collect::<Vec<u8>>();
+				let device = devices.get_mut(&address);
+
+				match (mode & 0x18, device) {
+					(0x00, Some((memory, pointer))) => {
+						if let Some((register, data)) = args.split_first() {
+							*pointer = usize::from(*register);
+							let len = memory.len();
+							for (offset, datum) in data.iter().enumerate() {
+								memory[(*pointer + offset) % len] = *datum;
+							}
+						}
+					},
+					(0x08, device) => {
+						// the firmware fills in a dummy register of 0 when none was sent
+							let (register, size) = match args[..] {
+								[register, size] => (Some(register), size),
+								[size] => (None, size),
+								_ => continue,
+							};
+
+						let data = match device {
+							Some((memory, pointer)) => {
+								if let Some(register) = register {
+									*pointer = usize::from(register);
+								}
+								(0..usize::from(size)).map(|offset| memory[(*pointer + offset) % memory.len()]).collect()
+							},
+							None => {
+								let _ = incoming.send(sysex(STRING_DATA, b"I2C: Too few bytes received"));
+								vec![]
+							},
+						};
+
+						let mut reply = vec![address as u8, register.unwrap_or(0)];
+						reply.extend(data);
+						let Ok(()) = incoming.send(sysex(I2C_REPLY, &reply)) else { break; };
+					},
+					_ => {},
+				}
+			}
+		});
+
+		board
+	}
+
+	#[test]
+	fn read_without_register_matches_the_firmware_reply() {
+		let (mut board, incoming, _outgoing) = Board::detached();
+		incoming.send(vec![START_SYSEX, I2C_REPLY, 0x48, 0x00, 0x00, 0x00, 0x12, 0x00, 0x7F, 0x01, END_SYSEX]).unwrap();
+
+		let reply = board.i2c_read_request(&I2CRequest::read(0x48, 2), TIMEOUT).unwrap();
+
+		assert_eq!(reply.address, 0x48);
+		assert_eq!(reply.data, vec![0x12, 0xFF]);
+	}
+
+	#[test]
+	fn read_with_register_skips_replies_from_other_registers() {
+		let (mut board, incoming, _outgoing) = Board::detached();
+		incoming.send(vec![
+			START_SYSEX, I2C_REPLY, 0x48, 0x00, 0x05, 0x00, 0x01, 0x00, END_SYSEX,
+			START_SYSEX, I2C_REPLY, 0x48, 0x00, 0x06, 0x00, 0x02, 0x00, END_SYSEX,
+		]).unwrap();
+
+		let reply = board.i2c_read_register(0x48, 0x06, 1, TIMEOUT).unwrap();
+
+		assert_eq!(reply.data, vec![0x02]);
+		assert_eq!(board.take_i2c_reply(0x48).map(|reply| reply.data), Some(vec![0x01]));
+	}
+
+	#[test]
+	fn read_without_register_reads_from_the_device() {
+		let mut board = board_with_devices(&[(0x48, &[0x10, 0x20, 0x30, 0x40])]);
+
+		board.i2c_write(0x48, &[0x02]).unwrap();
+		let reply = board.i2c_read_request(&I2CRequest::read(0x48, 2), TIMEOUT).unwrap();
+
+		assert_eq!(reply.data, vec![0x30, 0x40]);
+	}
+
+	#[test]
+	fn scan_finds_present_devices_and_skips_absent_ones() {
+		let mut board = board_with_devices(&[(0x3C, &[0x00]), (0x68, &[0x00])]);
+
+		assert_eq!(board.i2c_scan(TIMEOUT).unwrap(), vec![0x3C, 0x68]);
+	}
+}
@@ -1,5 +1,19 @@
 #![allow(clippy::cast_possible_truncation)]
 
+use std::collections::{
+	HashMap,
+	VecDeque
+};
+use std::sync::atomic::{
+	AtomicU64,
+	Ordering
+};
+use std::sync::mpsc::Sender;
+use std::time::{
+	Duration,
+	Instant
+};
+
 use serialport::SerialPortBuilder;
 
 use crate::ConnectionWrapper;
@@ -9,10 +23,8 @@ use crate::constants::{
     CAPABILITY_QUERY,
     DIGITAL_MESSAGE,
     END_SYSEX,
-    I2C_CONFIG,
-    I2C_READ,
-    I2C_REQUEST,
-    I2C_WRITE,
+    EXTENDED_ANALOG,
+    PIN_STATE_QUERY,
     REPORT_ANALOG,
     REPORT_DIGITAL,
     REPORT_FIRMWARE,
@@ -21,45 +33,140 @@ use crate::constants::{
     START_SYSEX
 };
 use crate::types::{
+    AnalogPinInfo,
     Error,
+    HandshakeStage,
     I2CReply,
+    Message,
     Pin,
+    PinMode,
+    PinRef,
     Result,
+    SerialPortId,
 };
 
+/// Source of the ids that tie pin handles to the board that made them.
+static NEXT_BOARD_ID: AtomicU64 = AtomicU64::new(0);
+
 /// A Firmata board representation.
 // definition
 	#[derive(Debug)]
 	pub struct Board {
+		id: u64,
 		connection_wrapper: ConnectionWrapper,
 		buffer: Vec<u8>,
 		initial_messages_sent: bool,
+		analog_mapping_received: bool,
 
 		firmware_name: Option<String>,
 		firmware_version: Option<String>,
 		protocol_version: Option<String>,
 		pins: Vec<Pin>,
-		i2c_data: Vec<I2CReply>,
+		i2c_data: HashMap<u16, I2CReplies>,
+		i2c_data_limit: usize,
+		i2c_waiters: Vec<I2CWaiter>,
+		next_i2c_waiter: u64,
+		pin_mode_checks: bool,
+		i2c_subscriptions: Vec<(u16, u8, Sender<I2CReply>)>,
+		encoder_positions: HashMap<u8, i32>,
+		onewire_correlation_id: u16,
+		pending_messages: Vec<Message>,
+		serial_data: HashMap<SerialPortId, VecDeque<u8>>,
+		task_recording: Option<Vec<u8>>,
+		callbacks: Callbacks,
 	}
 
 // creation
 	impl Board {
+		/// How often `connect` asks again for the firmware report while waiting for it.
+		const FIRMWARE_REQUEST_INTERVAL: Duration = Duration::from_secs(1);
+
 		#[must_use]
 		pub fn new(serial_port_builder: SerialPortBuilder) -> Board {
+			Board::with_connection(ConnectionWrapper::new(serial_port_builder))
+		}
+
+		pub(crate) fn with_connection(connection_wrapper: ConnectionWrapper) -> Board {
 			Board {
-				connection_wrapper: ConnectionWrapper::new(serial_port_builder),
+				id: NEXT_BOARD_ID.fetch_add(1, Ordering::Relaxed),
+				connection_wrapper,
 				buffer: vec![],
 				initial_messages_sent: false,
+				analog_mapping_received: false,
 
 				firmware_name: None,
 				firmware_version: None,
 				protocol_version: None,
 				pins: vec![],
-				i2c_data: vec![],
+				i2c_data: HashMap::new(),
+				i2c_data_limit: Board::DEFAULT_I2C_DATA_LIMIT,
+				i2c_waiters: vec![],
+				next_i2c_waiter: 0,
+				pin_mode_checks: true,
+				i2c_subscriptions: vec![],
+				encoder_positions: HashMap::new(),
+				onewire_correlation_id: 0,
+				pending_messages: vec![],
+				serial_data: HashMap::new(),
+				task_recording: None,
+				callbacks: Callbacks::default(),
+			}
+		}
+
+		/// Open the board and wait up to `timeout` for the firmware, capability and analog mapping
+		/// handshake to finish, returning a board that is ready to use.
+		///
+		/// A timeout is reported as `Error::HandshakeTimeout` with the stage that did not finish.
+		#[tracing::instrument(err, level = "DEBUG")]
+		pub fn connect(serial_port_builder: SerialPortBuilder, timeout: Duration) -> Result<Board> {
+			let mut board = Board::new(serial_port_builder);
+			let start = Instant::now();
+			let mut last_firmware_request = start;
+
+			while !board.is_ready() {
+				board.poll()?;
+
+				let stage = board.handshake_stage();
+
+				if start.elapsed() >= timeout {
+					return Err(Error::HandshakeTimeout { stage });
+				}
+
+				// boards that do not reset when the port opens will have sent their firmware report already
+					if stage == HandshakeStage::Firmware && last_firmware_request.elapsed() >= Board::FIRMWARE_REQUEST_INTERVAL {
+						board.report_firmware()?;
+						last_firmware_request = Instant::now();
+					}
+
+				std::thread::sleep(Duration::from_millis(10));
+			}
+
+			Ok(board)
+		}
+
+		/// The stage of the handshake the board is waiting on.
+		pub(crate) fn handshake_stage(&self) -> HandshakeStage {
+			if self.firmware_name.is_none() {
+				HandshakeStage::Firmware
+			} else if self.pins.is_empty() {
+				HandshakeStage::Capabilities
+			} else {
+				HandshakeStage::AnalogMapping
 			}
 		}
 	}
 
+// testing
+	#[cfg(test)]
+	impl Board {
+		/// A board over a detached connection, along with the sender for bytes coming from the board and
+		/// the receiver for bytes sent to it.
+		pub(crate) fn detached() -> (Board, Sender<Vec<u8>>, std::sync::mpsc::Receiver<Vec<u8>>) {
+			let (connection_wrapper, incoming, outgoing) = ConnectionWrapper::detached();
+			(Board::with_connection(connection_wrapper), incoming, outgoing)
+		}
+	}
+
 // disconnect
 	impl Board {
 		pub fn disconnect(&mut self) -> Result<()> {
@@ -69,9 +176,41 @@ use crate::types::{
 
 // tools
 	impl Board {
-		/// Write on the internal connection.
+		/// Modes that take an analog write.
+		const ANALOG_WRITE_MODES: &'static [PinMode] = &[PinMode::Pwm, PinMode::Servo];
+
+		/// Check that `pin` is in one of the `expected` modes, unless pin mode checks are turned off.
+		fn check_pin_mode(&self, pin: u8, expected: &[PinMode], source: &str) -> Result<()> {
+			if self.pin_mode_checks {
+				self.require_pin_mode(pin, expected, source)
+			} else {
+				self.get_pin(pin as usize)
+					.map(|_| ())
+					.ok_or_else(|| Error::PinOutOfBounds { pin, len: self.pins.len(), source: source.to_string() })
+			}
+		}
+
+		/// Check that `pin` is in one of the `expected` modes, whether or not pin mode checks are turned on.
+		pub(crate) fn require_pin_mode(&self, pin: u8, expected: &[PinMode], source: &str) -> Result<()> {
+			let Some(p) = self.pins.get(pin as usize) else {
+				return Err(Error::PinOutOfBounds { pin, len: self.pins.len(), source: source.to_string() })
+			};
+
+			if !expected.contains(&p.mode) {
+				return Err(Error::WrongPinMode { pin, mode: p.mode, expected: expected.to_vec() });
+			}
+
+			Ok(())
+		}
+
+		/// Write on the internal connection, or into the task being recorded.
 		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
 		fn write_to_connection(&mut self, buf: &[u8]) -> Result<()> {
+			if let Some(recording) = self.task_recording.as_mut() {
+				recording.extend_from_slice(buf);
+				return Ok(());
+			}
+
 			self.connection_wrapper.write(buf.to_vec())?;
 			Ok(())
 		}
@@ -93,7 +232,7 @@ use crate::types::{
 		#[must_use]
 		/// Check if the connection to the board has been successfully established
 		pub fn is_ready(&self) -> bool {
-			self.initial_messages_sent && !self.pins.is_empty() && self.connection_wrapper.is_active()
+			self.initial_messages_sent && !self.pins.is_empty() && self.analog_mapping_received && self.connection_wrapper.is_active()
 		}
 
 		#[must_use]
@@ -109,9 +248,38 @@ use crate::types::{
 		}
 
 		#[must_use]
-		/// Get the raw I2C replies that have been read from the board.
-		pub fn get_i2c_data(&self) -> &Vec<I2CReply> {
-			&self.i2c_data
+		/// Get the analog pins from the board's analog mapping, in channel order.
+		pub fn analog_pins(&self) -> Vec<AnalogPinInfo> {
+			let mut analog_pins = self.pins
+				.iter()
+				.enumerate()
+				.filter_map(|(pin, p)| p.analog_channel.map(|channel| AnalogPinInfo { name: format!("A{channel}"), pin: pin as u8, channel }))
+				.collect::<Vec<AnalogPinInfo>>();
+			analog_pins.sort_by_key(|info| info.channel);
+			analog_pins
+		}
+
+		/// Get the stored I2C replies from the device at `address`, oldest first.
+		pub fn get_i2c_data(&self, address: u16) -> impl Iterator<Item = &I2CReply> {
+			self.i2c_data.get(&address).into_iter().flat_map(|replies| replies.replies.iter())
+		}
+
+		#[must_use]
+		/// Get the number of I2C replies kept for each device before the oldest are dropped.
+		pub fn get_i2c_data_limit(&self) -> usize {
+			self.i2c_data_limit
+		}
+
+		#[must_use]
+		/// Get whether writes check that their pin is in a suitable mode.
+		pub fn get_pin_mode_checks(&self) -> bool {
+			self.pin_mode_checks
+		}
+
+		#[must_use]
+		/// Get the last reported position of encoder `id`.
+		pub fn get_encoder_position(&self, id: u8) -> Option<i32> {
+			self.encoder_positions.get(&id).copied()
 		}
 
 		#[must_use]
@@ -135,21 +303,28 @@ use crate::types::{
 
 // set
 	impl Board {
+		/// Turn the pin mode checks made by writes on or off. With them off, writes are sent whatever mode
+		/// their pin is in, as for firmware that reports modes the board does not know about.
+		pub fn set_pin_mode_checks(&mut self, enabled: bool) {
+			self.pin_mode_checks = enabled;
+		}
+
 		/// Set the `mode` of the specified `pin`.
 		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
-		pub fn set_pin_mode(&mut self, pin_index: u8, mode: u8) -> Result<()> {
+		pub fn set_pin_mode(&mut self, pin: impl PinRef, mode: PinMode) -> Result<()> {
+			let pin_index = pin.resolve(self)?;
 			if let Some(pin) = self.pins.get_mut(pin_index as usize) {
 
 				if !pin.modes.contains(&mode) {
-					return Err(Error::InvalidPinMode { pin:pin_index, modes:pin.modes.clone() });
+					return Err(Error::InvalidPinMode { pin:pin_index, mode, modes:pin.modes.clone() });
 				}
 
-				pin.mode = mode;
+				pin.set_mode(mode);
 			} else {
 				return Err(Error::PinOutOfBounds { pin:pin_index, len: self.pins.len(), source: "set_pin_mode".to_string() })
 			}
 
-			self.write_to_connection(&[SET_PIN_MODE, pin_index, mode])
+			self.write_to_connection(&[SET_PIN_MODE, pin_index, mode.into()])
 		}
 	}
 
@@ -166,58 +341,16 @@ use crate::types::{
 		pub fn query_capabilities(&mut self) -> Result<()> {
 			self.write_to_connection(&[START_SYSEX, CAPABILITY_QUERY, END_SYSEX])
 		}
-	}
-	
-// i2c
-	impl Board {
-		/// Configure the `delay` in microseconds for I2C devices that require a delay between when the
-		/// register is written to and the data in that register can be read.
-		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
-		pub fn i2c_config(&mut self, delay: u16) -> Result<()> {
-			self.write_to_connection(&[
-				START_SYSEX,
-				I2C_CONFIG,
-				(delay & 0xFF) as u8,
-				(delay >> 8 & 0xFF) as u8,
-				END_SYSEX,
-			])
-		}
 
-    	/// Read `size` bytes from I2C device at the specified `address`.
+		/// Query the board for the mode and value of `pin`, which arrive as `Message::PinStateResponse`
+		/// and update the local pin.
 		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
-		pub fn i2c_read(&mut self, address: u8, size: u8) -> Result<()> {
-			self.write_to_connection(&[
-				START_SYSEX,
-				I2C_REQUEST,
-				address,
-				I2C_READ << 3,
-				size & SYSEX_REALTIME,
-				(u16::from(size) >> 7) as u8 & SYSEX_REALTIME,
-				END_SYSEX,
-			])
-		}
-
-    	/// Write `data` to the I2C device at the specified `address`.
-		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
-		pub fn i2c_write(&mut self, address: u8, data: &[u8]) -> Result<()> {
-			let mut buf = vec![
-				START_SYSEX,
-				I2C_REQUEST,
-				address,
-				I2C_WRITE << 3
-			];
-
-			for datum in data {
-				buf.push(datum & SYSEX_REALTIME);
-				buf.push((u16::from(*datum) >> 7) as u8 & SYSEX_REALTIME);
-			}
-
-			buf.push(END_SYSEX);
-
-			self.write_to_connection(&buf)
+		pub fn query_pin_state(&mut self, pin: impl PinRef) -> Result<()> {
+			let pin = pin.resolve(self)?;
+			self.write_to_connection(&[START_SYSEX, PIN_STATE_QUERY, pin, END_SYSEX])
 		}
 	}
-
+	
 // report
 	impl Board {
 		/// Query the board for current firmware and protocol information.
@@ -228,7 +361,9 @@ use crate::types::{
 
     	/// Set the analog reporting `state` of the specified `pin`.
 		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
-		pub fn report_analog(&mut self, pin: u8, state: bool) -> Result<()> {
+		pub fn report_analog(&mut self, pin: impl PinRef, state: bool) -> Result<()> {
+			let pin = pin.resolve(self)?;
+
 			// get pin
 				let p = if let Some(pin) = self.pins.get_mut(pin as usize) {
 					pin
@@ -241,27 +376,23 @@ use crate::types::{
 					return Ok(());
 				}
 
+			// analog reporting is by channel rather than by pin
+				let Some(channel) = p.analog_channel else {
+					return Err(Error::InvalidPinMode { pin, mode: PinMode::Analog, modes: p.modes.clone() });
+				};
+
 			// update state
 				p.report_analog_active = state;
 
-			// get port
-				let port = pin / 8;
-
-			// check other pins in this port and generate OR'd state for port
-				let mut new_state = false;
-				for pin_index in 0..8 {
-					if let Some(pin) = self.pins.get(pin_index) {
-						new_state ^= pin.report_analog_active;
-					}
-				}
-
 			// send message to board
-				self.write_to_connection(&[REPORT_ANALOG | port, u8::from(new_state)])
+				self.write_to_connection(&[REPORT_ANALOG | channel, u8::from(state)])
 		}
 
     	/// Set the digital reporting `state` of the specified `pin`.
 		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
-		pub fn report_digital(&mut self, pin: u8, state: bool) -> Result<()> {
+		pub fn report_digital(&mut self, pin: impl PinRef, state: bool) -> Result<()> {
+			let pin = pin.resolve(self)?;
+
 			// get pin
 				let p = if let Some(pin) = self.pins.get_mut(pin as usize) {
 					pin
@@ -295,11 +426,14 @@ use crate::types::{
 
 // write
 	impl Board {
-		/// Write `level` to the analog `pin`.
+		/// Write `level` to the analog `pin`, which must be in `PinMode::Pwm` or `PinMode::Servo`.
 		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
-		pub fn analog_write(&mut self, pin: u8, level: u8) -> Result<()> {
+		pub fn analog_write(&mut self, pin: impl PinRef, level: u8) -> Result<()> {
+			let pin = pin.resolve(self)?;
+			self.check_pin_mode(pin, Board::ANALOG_WRITE_MODES, "analog_write")?;
+
 			if let Some(pin) = self.pins.get_mut(pin as usize) {
-				pin.value = level;
+				pin.value = u16::from(level);
 			} else {
 				return Err(Error::PinOutOfBounds { pin, len: self.pins.len(), source: "analog_write".to_string() })
 			}
@@ -311,13 +445,62 @@ use crate::types::{
 			])
 		}
 
-    	/// Write `level` to the digital `pin`.
+		/// Write `value` to the `pin` using an `EXTENDED_ANALOG` message, which allows for pins above 15 and
+		/// values wider than 14 bits.
 		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
-		pub fn digital_write(&mut self, pin: u8, level: bool) -> Result<()> {
+		pub fn extended_analog_write(&mut self, pin: impl PinRef, value: u16) -> Result<()> {
+			let pin = pin.resolve(self)?;
+			self.check_pin_mode(pin, Board::ANALOG_WRITE_MODES, "extended_analog_write")?;
+
+			if let Some(pin) = self.pins.get_mut(pin as usize) {
+				pin.value = value;
+			} else {
+				return Err(Error::PinOutOfBounds { pin, len: self.pins.len(), source: "extended_analog_write".to_string() })
+			}
+
+			self.write_to_connection(&[
+				START_SYSEX,
+				EXTENDED_ANALOG,
+				pin,
+				value as u8 & SYSEX_REALTIME,
+				(value >> 7) as u8 & SYSEX_REALTIME,
+				(value >> 14) as u8 & SYSEX_REALTIME,
+				END_SYSEX,
+			])
+		}
+
+		/// Write a `value` of up to 16 bits to `pin`, only falling back to `EXTENDED_ANALOG` when a standard
+		/// analog message can not carry it.
+		pub(crate) fn write_analog_value(&mut self, pin: u8, value: u16) -> Result<()> {
+			if pin > 0x0F || value > 0x3FFF {
+				return self.extended_analog_write(pin, value);
+			}
+
+			self.check_pin_mode(pin, Board::ANALOG_WRITE_MODES, "write_analog_value")?;
+
+			if let Some(pin) = self.pins.get_mut(pin as usize) {
+				pin.value = value;
+			} else {
+				return Err(Error::PinOutOfBounds { pin, len: self.pins.len(), source: "write_analog_value".to_string() })
+			}
+
+			self.write_to_connection(&[
+				ANALOG_MESSAGE | pin,
+				value as u8 & SYSEX_REALTIME,
+				(value >> 7) as u8 & SYSEX_REALTIME,
+			])
+		}
+
+    	/// Write `level` to the digital `pin`, which must be in `PinMode::Output`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn digital_write(&mut self, pin: impl PinRef, level: bool) -> Result<()> {
+			let pin = pin.resolve(self)?;
+			self.check_pin_mode(pin, &[PinMode::Output], "digital_write")?;
+
 			let port = (pin/8) as usize;
 			
 			if let Some(pin) = self.pins.get_mut(pin as usize) {
-				pin.value = u8::from(level);
+				pin.value = u16::from(level);
 			} else {
 				return Err(Error::PinOutOfBounds { pin, len: self.pins.len(), source: "digital_write".to_string() })
 			}
@@ -341,4 +524,66 @@ use crate::types::{
 		}
 	}
 
-mod poll;
\ No newline at end of file
+// normalized
+	impl Board {
+		/// Modes whose value is a level that can be read as a fraction of the mode's resolution.
+		const NORMALIZED_READ_MODES: &'static [PinMode] = &[PinMode::Input, PinMode::Pullup, PinMode::Output, PinMode::Analog, PinMode::Pwm];
+		/// Modes whose value is a level that can be written as a fraction of the mode's resolution. Servo
+		/// values are angles or pulse widths rather than levels, so servos are driven through [`Servo`].
+		const NORMALIZED_WRITE_MODES: &'static [PinMode] = &[PinMode::Output, PinMode::Pwm];
+
+		/// Read the value of `pin` as a fraction from 0 to 1 of the largest value it takes at the
+		/// resolution of its current mode. The pin must be a digital or analog input, or a digital or PWM
+		/// output.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn read_normalized(&self, pin: impl PinRef) -> Result<f32> {
+			let pin = pin.resolve(self)?;
+			self.require_pin_mode(pin, Board::NORMALIZED_READ_MODES, "read_normalized")?;
+
+			let p = &self.pins[pin as usize];
+			Ok((f64::from(p.value) / f64::from(p.get_max_value())).min(1.0) as f32)
+		}
+
+		/// Write `value`, a fraction from 0 to 1, to `pin` scaled to the resolution of its current mode.
+		/// The pin must be in `PinMode::Pwm`, or in `PinMode::Output`, where it is written high from one
+		/// half upwards.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn write_normalized(&mut self, pin: impl PinRef, value: f32) -> Result<()> {
+			let pin = pin.resolve(self)?;
+			self.require_pin_mode(pin, Board::NORMALIZED_WRITE_MODES, "write_normalized")?;
+
+			let p = &self.pins[pin as usize];
+			let max_value = p.get_max_value();
+			let value = (f64::from(value.clamp(0.0, 1.0)) * f64::from(max_value)).round() as u32;
+
+			if p.mode == PinMode::Output {
+				return self.digital_write(pin, value * 2 >= max_value);
+			}
+
+			self.write_analog_value(pin, value.min(u32::from(u16::MAX)) as u16)
+		}
+	}
+
+mod callbacks;
+use callbacks::Callbacks;
+pub use callbacks::CallbackHandle;
+mod encoder;
+mod i2c;
+use i2c::{I2CReplies, I2CWaiter};
+pub use i2c::I2CDevice;
+#[cfg(all(test, feature = "embedded-hal"))]
+pub(crate) use i2c::tests::board_with_devices;
+mod onewire;
+pub use onewire::Ds18b20;
+mod pin_handles;
+pub use pin_handles::{AnalogInput, DigitalInput, DigitalOutput, PwmOutput};
+mod poll;
+mod scheduler;
+mod serial;
+pub use serial::SerialStream;
+mod servo;
+pub use servo::Servo;
+mod shift;
+pub use shift::ShiftRegisterChain;
+mod stepper;
+pub use stepper::{Stepper, StepperGroup};
\ No newline at end of file
@@ -0,0 +1,288 @@
+#![allow(clippy::cast_possible_truncation)]
+
+use std::time::Duration;
+
+use crate::constants::{
+    END_SYSEX,
+    ONEWIRE_CONFIG_REQUEST,
+    ONEWIRE_DATA,
+    ONEWIRE_DELAY_REQUEST_BIT,
+    ONEWIRE_READ_REQUEST_BIT,
+    ONEWIRE_RESET_REQUEST_BIT,
+    ONEWIRE_SEARCH_ALARMS_REQUEST,
+    ONEWIRE_SEARCH_REQUEST,
+    ONEWIRE_SELECT_REQUEST_BIT,
+    ONEWIRE_SKIP_REQUEST_BIT,
+    ONEWIRE_WRITE_REQUEST_BIT,
+    START_SYSEX
+};
+use crate::encoding::encode_7bit;
+use crate::types::{
+    Error,
+    Message,
+    PinMode,
+    PinRef,
+    OneWireRequest,
+    Result,
+};
+
+use super::Board;
+
+// onewire
+	impl Board {
+		/// Configure `pin` as a `OneWire` bus, with `parasitic_power` keeping the bus powered after
+		/// writes. This also places the pin in `PinMode::OneWire`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn onewire_config(&mut self, pin: impl PinRef, parasitic_power: bool) -> Result<()> {
+			let pin = pin.resolve(self)?;
+
+			if let Some(p) = self.pins.get_mut(pin as usize) {
+				if !p.modes.contains(&PinMode::OneWire) {
+					return Err(Error::InvalidPinMode { pin, mode: PinMode::OneWire, modes: p.modes.clone() });
+				}
+
+				p.set_mode(PinMode::OneWire);
+			} else {
+				return Err(Error::PinOutOfBounds { pin, len: self.pins.len(), source: "onewire_config".to_string() })
+			}
+
+			self.write_to_connection(&[START_SYSEX, ONEWIRE_DATA, ONEWIRE_CONFIG_REQUEST, pin, u8::from(parasitic_power), END_SYSEX])
+		}
+
+		/// Search the bus on `pin` for devices, returning their 64-bit ROM addresses.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn onewire_search(&mut self, pin: impl PinRef, timeout: Duration) -> Result<Vec<u64>> {
+			let pin = pin.resolve(self)?;
+
+			self.write_to_connection(&[START_SYSEX, ONEWIRE_DATA, ONEWIRE_SEARCH_REQUEST, pin, END_SYSEX])?;
+
+			self.poll_until(timeout, "onewire_search", |_, message| match message {
+				Message::OneWireSearchReply { pin: reply_pin, addresses } if *reply_pin == pin => Some(addresses.clone()),
+				_ => None,
+			})
+		}
+
+		/// Search the bus on `pin` for devices in an alarm state, returning their 64-bit ROM addresses.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn onewire_search_alarms(&mut self, pin: impl PinRef, timeout: Duration) -> Result<Vec<u64>> {
+			let pin = pin.resolve(self)?;
+
+			self.write_to_connection(&[START_SYSEX, ONEWIRE_DATA, ONEWIRE_SEARCH_ALARMS_REQUEST, pin, END_SYSEX])?;
+
+			self.poll_until(timeout, "onewire_search_alarms", |_, message| match message {
+				Message::OneWireSearchAlarmsReply { pin: reply_pin, addresses } if *reply_pin == pin => Some(addresses.clone()),
+				_ => None,
+			})
+		}
+
+		/// Send a `request` to the bus on `pin`. When the request reads, the data arrives as a
+		/// `Message::OneWireReadReply` carrying the request's correlation id.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn onewire_request(&mut self, pin: impl PinRef, request: &OneWireRequest) -> Result<()> {
+			let pin = pin.resolve(self)?;
+
+			let mut command = 0;
+			if request.reset { command |= ONEWIRE_RESET_REQUEST_BIT; }
+			if request.skip { command |= ONEWIRE_SKIP_REQUEST_BIT; }
+			if request.select.is_some() { command |= ONEWIRE_SELECT_REQUEST_BIT; }
+			if request.read > 0 { command |= ONEWIRE_READ_REQUEST_BIT; }
+			if request.delay > 0 { command |= ONEWIRE_DELAY_REQUEST_BIT; }
+			if !request.write.is_empty() { command |= ONEWIRE_WRITE_REQUEST_BIT; }
+
+			// the payload fields sit at fixed offsets, so only trailing unused fields can be left out
+				let mut payload = vec![];
+				if request.select.is_some() || request.read > 0 || request.delay > 0 || !request.write.is_empty() {
+					payload.extend(request.select.unwrap_or(0).to_le_bytes());
+				}
+				if request.read > 0 || request.delay > 0 || !request.write.is_empty() {
+					payload.extend(request.read.to_le_bytes());
+					payload.extend(request.correlation_id.to_le_bytes());
+				}
+				if request.delay > 0 || !request.write.is_empty() {
+					payload.extend(request.delay.to_le_bytes());
+				}
+				payload.extend(&request.write);
+
+			let mut buf = vec![START_SYSEX, ONEWIRE_DATA, command, pin];
+			buf.extend(encode_7bit(&payload));
+			buf.push(END_SYSEX);
+
+			self.write_to_connection(&buf)
+		}
+
+		/// Reset the bus on `pin`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn onewire_reset(&mut self, pin: impl PinRef) -> Result<()> {
+			let pin = pin.resolve(self)?;
+
+			self.onewire_request(pin, &OneWireRequest { reset: true, ..Default::default() })
+		}
+
+		/// Address the device with ROM `address` on the bus on `pin`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn onewire_select(&mut self, pin: impl PinRef, address: u64) -> Result<()> {
+			let pin = pin.resolve(self)?;
+
+			self.onewire_request(pin, &OneWireRequest { select: Some(address), ..Default::default() })
+		}
+
+		/// Address all devices on the bus on `pin`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn onewire_skip(&mut self, pin: impl PinRef) -> Result<()> {
+			let pin = pin.resolve(self)?;
+
+			self.onewire_request(pin, &OneWireRequest { skip: true, ..Default::default() })
+		}
+
+		/// Write `data` to the bus on `pin`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn onewire_write(&mut self, pin: impl PinRef, data: &[u8]) -> Result<()> {
+			let pin = pin.resolve(self)?;
+
+			self.onewire_request(pin, &OneWireRequest { write: data.to_vec(), ..Default::default() })
+		}
+
+		/// Read `len` bytes from the bus on `pin`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn onewire_read(&mut self, pin: impl PinRef, len: u16, timeout: Duration) -> Result<Vec<u8>> {
+			let pin = pin.resolve(self)?;
+
+			self.onewire_transfer(pin, OneWireRequest { read: len, ..Default::default() }, timeout)
+		}
+
+		/// Send a reading `request` to the bus on `pin` under a fresh correlation id, and wait for the
+		/// data it reads.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn onewire_transfer(&mut self, pin: impl PinRef, request: OneWireRequest, timeout: Duration) -> Result<Vec<u8>> {
+			let pin = pin.resolve(self)?;
+
+			self.onewire_correlation_id = self.onewire_correlation_id.wrapping_add(1);
+			let correlation_id = self.onewire_correlation_id;
+
+			self.onewire_request(pin, &OneWireRequest { correlation_id, ..request })?;
+
+			self.poll_until(timeout, "onewire_transfer", |_, message| match message {
+				Message::OneWireReadReply { pin: reply_pin, correlation_id: reply_id, data }
+					if *reply_pin == pin && *reply_id == correlation_id => Some(data.clone()),
+				_ => None,
+			})
+		}
+	}
+
+/// A DS18B20 temperature sensor on a `OneWire` bus.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ds18b20 {
+	pin: u8,
+	address: u64,
+}
+
+// creation
+	impl Ds18b20 {
+		/// The `OneWire` family code of the DS18B20.
+		pub const FAMILY_CODE: u8 = 0x28;
+		/// How long a temperature conversion takes at the default 12-bit resolution.
+		pub const CONVERSION_TIME: Duration = Duration::from_millis(750);
+
+		/// The sensor at `address` on the bus on `pin`.
+		pub fn new(board: &Board, pin: impl PinRef, address: u64) -> Result<Self> {
+			Ok(Self { pin: pin.resolve(board)?, address })
+		}
+
+		/// Search the bus on `pin` and return every DS18B20 found on it. The bus must already be
+		/// configured with [`Board::onewire_config`].
+		pub fn search(board: &mut Board, pin: impl PinRef, timeout: Duration) -> Result<Vec<Ds18b20>> {
+			let pin = pin.resolve(board)?;
+
+			Ok(
+				board.onewire_search(pin, timeout)?
+					.into_iter()
+					.filter(|address| *address as u8 == Self::FAMILY_CODE)
+					.map(|address| Ds18b20 { pin, address })
+					.collect()
+			)
+		}
+	}
+
+// get
+	impl Ds18b20 {
+		#[must_use]
+		/// Get the pin of the bus the sensor is on.
+		pub fn pin(&self) -> u8 {
+			self.pin
+		}
+
+		#[must_use]
+		/// Get the ROM address of the sensor.
+		pub fn address(&self) -> u64 {
+			self.address
+		}
+	}
+
+// read
+	impl Ds18b20 {
+		/// Start a conversion, wait for it to finish, then read back the temperature in degrees Celsius.
+		pub fn read_celsius(&self, board: &mut Board, timeout: Duration) -> Result<f32> {
+			const CONVERT_T: u8 = 0x44;
+			const READ_SCRATCHPAD: u8 = 0xBE;
+
+			board.onewire_request(self.pin, &OneWireRequest {
+				reset: true,
+				select: Some(self.address),
+				write: vec![CONVERT_T],
+				..Default::default()
+			})?;
+
+			std::thread::sleep(Self::CONVERSION_TIME);
+
+			let scratchpad = board.onewire_transfer(self.pin, OneWireRequest {
+				reset: true,
+				select: Some(self.address),
+				write: vec![READ_SCRATCHPAD],
+				read: 9,
+				..Default::default()
+			}, timeout)?;
+
+			if scratchpad.len() != 9 || crc8(&scratchpad[..8]) != scratchpad[8] {
+				return Err(Error::OneWireCrcMismatch { address: self.address });
+			}
+
+			Ok(f32::from(i16::from_le_bytes([scratchpad[0], scratchpad[1]])) / 16.0)
+		}
+	}
+
+/// The Dallas/Maxim CRC-8 used to check `OneWire` data.
+fn crc8(data: &[u8]) -> u8 {
+	let mut crc = 0u8;
+	for byte in data {
+		let mut byte = *byte;
+		for _ in 0..8 {
+			let mix = (crc ^ byte) & 0x01;
+			crc >>= 1;
+			if mix != 0 {
+				crc ^= 0x8C;
+			}
+			byte >>= 1;
+		}
+	}
+	crc
+}
+
+#[cfg(test)]
+mod tests {
+	use super::crc8;
+
+	#[test]
+	fn crc8_matches_known_values() {
+		assert_eq!(crc8(&[]), 0x00);
+		assert_eq!(crc8(b"123456789"), 0xA1);
+		// the ROM code from Maxim application note 27
+			assert_eq!(crc8(&[0x02, 0x1C, 0xB8, 0x01, 0x00, 0x00, 0x00]), 0xA2);
+	}
+
+	#[test]
+	fn crc8_of_data_followed_by_its_crc_is_zero() {
+		let data = [0x28, 0xFF, 0x4B, 0x6E, 0x91, 0x16, 0x04];
+		let crc = crc8(&data);
+		assert_eq!(crc8(&[&data[..], &[crc]].concat()), 0x00);
+	}
+}
@@ -0,0 +1,188 @@
+use crate::types::{
+    Error,
+    PinMode,
+    PinRef,
+    Result,
+};
+
+use super::Board;
+
+// handles
+	impl Board {
+		/// Place `pin` in `PinMode::Output` and return a handle for writing to it.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn digital_output(&mut self, pin: impl PinRef) -> Result<DigitalOutput> {
+			let pin = pin.resolve(self)?;
+
+			self.set_pin_mode(pin, PinMode::Output)?;
+			Ok(DigitalOutput { board: self.id, pin })
+		}
+
+		/// Place `pin` in `PinMode::Input`, turn on digital reporting for it, and return a handle for
+		/// reading it.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn digital_input(&mut self, pin: impl PinRef) -> Result<DigitalInput> {
+			let pin = pin.resolve(self)?;
+
+			self.set_pin_mode(pin, PinMode::Input)?;
+			self.report_digital(pin, true)?;
+			Ok(DigitalInput { board: self.id, pin })
+		}
+
+		/// Place `pin` in `PinMode::Pullup`, turn on digital reporting for it, and return a handle for
+		/// reading it.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn digital_input_pullup(&mut self, pin: impl PinRef) -> Result<DigitalInput> {
+			let pin = pin.resolve(self)?;
+
+			self.set_pin_mode(pin, PinMode::Pullup)?;
+			self.report_digital(pin, true)?;
+			Ok(DigitalInput { board: self.id, pin })
+		}
+
+		/// Place `pin` in `PinMode::Analog`, turn on analog reporting for it, and return a handle for
+		/// reading it.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn analog_input(&mut self, pin: impl PinRef) -> Result<AnalogInput> {
+			let pin = pin.resolve(self)?;
+
+			self.set_pin_mode(pin, PinMode::Analog)?;
+			self.report_analog(pin, true)?;
+			Ok(AnalogInput { board: self.id, pin })
+		}
+
+		/// Place `pin` in `PinMode::Pwm` and return a handle for writing to it.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn pwm_output(&mut self, pin: impl PinRef) -> Result<PwmOutput> {
+			let pin = pin.resolve(self)?;
+
+			self.set_pin_mode(pin, PinMode::Pwm)?;
+			Ok(PwmOutput { board: self.id, pin })
+		}
+
+		/// Check that a handle for `pin` was made by this board, and that the pin is still in one of the
+		/// handle's `modes`. This is done whether or not pin mode checks are turned on.
+		fn check_handle(&self, board: u64, pin: u8, modes: &[PinMode], source: &str) -> Result<()> {
+			if board != self.id {
+				return Err(Error::ForeignHandle { pin });
+			}
+			self.require_pin_mode(pin, modes, source)
+		}
+
+		/// Get the last value reported for `pin`.
+		fn reported_value(&self, pin: u8) -> u16 {
+			self.pins[pin as usize].value
+		}
+	}
+
+/// A pin in `PinMode::Output`, created with [`Board::digital_output`].
+///
+/// Like the other pin handles, it only works with the board that created it, and fails with
+/// `Error::WrongPinMode` once the pin is placed in another mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DigitalOutput {
+	board: u64,
+	pin: u8,
+}
+
+impl DigitalOutput {
+	#[must_use]
+	/// Get the pin being written.
+	pub fn pin(&self) -> u8 {
+		self.pin
+	}
+
+	/// Write `level` to the pin.
+	pub fn write(&self, board: &mut Board, level: bool) -> Result<()> {
+		board.check_handle(self.board, self.pin, &[PinMode::Output], "DigitalOutput::write")?;
+		board.digital_write(self.pin, level)
+	}
+
+	/// Write the opposite of the level last written to the pin.
+	pub fn toggle(&self, board: &mut Board) -> Result<()> {
+		board.check_handle(self.board, self.pin, &[PinMode::Output], "DigitalOutput::toggle")?;
+		let level = board.reported_value(self.pin) == 0;
+		board.digital_write(self.pin, level)
+	}
+}
+
+/// A pin in `PinMode::Input` or `PinMode::Pullup`, created with [`Board::digital_input`] or
+/// [`Board::digital_input_pullup`].
+///
+/// Reads return the level last reported by the board, so the board must be polled to keep it current.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DigitalInput {
+	board: u64,
+	pin: u8,
+}
+
+impl DigitalInput {
+	#[must_use]
+	/// Get the pin being read.
+	pub fn pin(&self) -> u8 {
+		self.pin
+	}
+
+	/// Read the level of the pin.
+	pub fn read(&self, board: &Board) -> Result<bool> {
+		board.check_handle(self.board, self.pin, &[PinMode::Input, PinMode::Pullup], "DigitalInput::read")?;
+		Ok(board.reported_value(self.pin) != 0)
+	}
+}
+
+/// A pin in `PinMode::Analog`, created with [`Board::analog_input`].
+///
+/// Reads return the value last reported by the board, so the board must be polled to keep it current.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AnalogInput {
+	board: u64,
+	pin: u8,
+}
+
+impl AnalogInput {
+	#[must_use]
+	/// Get the pin being read.
+	pub fn pin(&self) -> u8 {
+		self.pin
+	}
+
+	/// Read the value of the pin.
+	pub fn read(&self, board: &Board) -> Result<u16> {
+		board.check_handle(self.board, self.pin, &[PinMode::Analog], "AnalogInput::read")?;
+		Ok(board.reported_value(self.pin))
+	}
+
+	/// Read the last value reported for the pin as a fraction from 0 to 1 of the board's analog
+	/// resolution.
+	pub fn read_normalized(&self, board: &Board) -> Result<f32> {
+		board.check_handle(self.board, self.pin, &[PinMode::Analog], "AnalogInput::read_normalized")?;
+		board.read_normalized(self.pin)
+	}
+}
+
+/// A pin in `PinMode::Pwm`, created with [`Board::pwm_output`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PwmOutput {
+	board: u64,
+	pin: u8,
+}
+
+impl PwmOutput {
+	#[must_use]
+	/// Get the pin being written.
+	pub fn pin(&self) -> u8 {
+		self.pin
+	}
+
+	/// Write the duty cycle `value` to the pin, at the board's PWM resolution.
+	pub fn write(&self, board: &mut Board, value: u16) -> Result<()> {
+		board.check_handle(self.board, self.pin, &[PinMode::Pwm], "PwmOutput::write")?;
+		board.write_analog_value(self.pin, value)
+	}
+
+	/// Write the duty cycle `value`, a fraction from 0 to 1, scaled to the board's PWM resolution.
+	pub fn write_normalized(&self, board: &mut Board, value: f32) -> Result<()> {
+		board.check_handle(self.board, self.pin, &[PinMode::Pwm], "PwmOutput::write_normalized")?;
+		board.write_normalized(self.pin, value)
+	}
+}
@@ -1,37 +1,121 @@
 #![allow(clippy::cast_possible_truncation)]
 
+use std::collections::HashMap;
+use std::time::{
+    Duration,
+    Instant
+};
+
 use crate::constants::{
+    ACCELSTEPPER_DATA,
+    ACCELSTEPPER_MOVE_COMPLETE,
+    ACCELSTEPPER_REPORT_POSITION,
     ANALOG_MAPPING_RESPONSE,
     ANALOG_MESSAGE,
     ANALOG_MESSAGE_BOUND,
     CAPABILITY_RESPONSE,
     DIGITAL_MESSAGE,
     DIGITAL_MESSAGE_BOUND,
+    ENCODER_CHANNEL_MASK,
+    ENCODER_DATA,
+    ENCODER_DIRECTION_MASK,
     END_SYSEX,
+    ERROR_TASK_REPLY,
     I2C_REPLY,
+    MULTISTEPPER_MOVE_COMPLETE,
+    ONEWIRE_DATA,
+    ONEWIRE_READ_REPLY,
+    ONEWIRE_SEARCH_ALARMS_REPLY,
+    ONEWIRE_SEARCH_REPLY,
 	PIN_MODE_IGNORE,
-    PIN_MODE_INPUT,
-	PIN_MODE_PULLUP,
     PIN_STATE_RESPONSE,
 	REPORT_DIGITAL,
     REPORT_FIRMWARE,
+    QUERY_ALL_TASKS_REPLY,
+    QUERY_TASK_REPLY,
     REPORT_VERSION,
-    START_SYSEX
+    SCHEDULER_DATA,
+    SERIAL_COMMAND_MASK,
+    SERIAL_DATA,
+    SERIAL_REPLY,
+    SHIFT_DATA,
+    SHIFT_IN_REPLY,
+    START_SYSEX,
+    STRING_DATA
+};
+use crate::encoding::{
+    decode_7bit,
+    decode_i32
 };
 use crate::types::{
     Error,
     I2CReply,
     Message,
     Pin,
+    PinMode,
     Result,
+    SchedulerTask,
+    SerialPortId,
 };
 
 use super::Board;
 
 // read incoming messages
 impl Board {
+	/// Read and decode everything the board has sent since the last call, including any messages that
+	/// arrived while waiting on a blocking request.
 	#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
 	pub fn poll(&mut self) -> Result<Vec<Message>> {
+		let mut messages = self.read_messages()?;
+		messages.splice(0..0, self.pending_messages.drain(..));
+		Ok(messages)
+	}
+
+	/// Read whatever the board has sent so that the local state is current, keeping the decoded
+	/// messages for the next call to `poll`.
+	#[cfg(feature = "embedded-hal")]
+	pub(crate) fn refresh(&mut self) -> Result<()> {
+		let mut messages = self.read_messages()?;
+		self.pending_messages.append(&mut messages);
+		Ok(())
+	}
+
+	/// Poll the board until `matcher` picks a result out of one of the decoded messages, or `timeout`
+	/// passes. The matched message is consumed, all others are kept for the next call to `poll`.
+	pub(crate) fn poll_until<T>(
+		&mut self,
+		timeout: Duration,
+		source: &str,
+		mut matcher: impl FnMut(&mut Board, &Message) -> Option<T>,
+	) -> Result<T> {
+		let start = Instant::now();
+
+		loop {
+			let mut result = None;
+			for message in self.read_messages()? {
+				if result.is_none() {
+					result = matcher(self, &message);
+					if result.is_some() {
+						continue;
+					}
+				}
+				self.pending_messages.push(message);
+			}
+
+			if let Some(result) = result {
+				return Ok(result);
+			}
+
+			if start.elapsed() >= timeout {
+				return Err(Error::Timeout { source: source.to_string() });
+			}
+
+			std::thread::sleep(Duration::from_millis(1));
+		}
+	}
+
+	#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+	fn read_messages(&mut self) -> Result<Vec<Message>> {
 		if self.firmware_name.is_some() && self.firmware_version.is_some() && !self.initial_messages_sent {
 			self.initial_messages_sent = true;
 			tracing::debug!("sending initial messages");
@@ -45,7 +129,7 @@ impl Board {
 
 		let mut messages = vec![];
 		while !self.buffer.is_empty() {
-			let Some(byte_0) = self.buffer.get(0) else { break; };
+			let Some(byte_0) = self.buffer.first() else { break; };
 
 			match *byte_0 {
 				REPORT_VERSION => {
@@ -66,17 +150,19 @@ impl Board {
 					let Some(byte_1) = self.buffer.get(1) else { break; };
 					let Some(byte_2) = self.buffer.get(2) else { break; };
 	
-					// extract pin info
-						let pin_index = (byte_0 & 0x0F) + 14;
-						let value = byte_1 | (byte_2 << 7);
+					// extract channel info
+						let channel = byte_0 & 0x0F;
+						let value = u16::from(*byte_1) | u16::from(*byte_2) << 7;
 	
-					// channel info into local data
-						let mut pin_updates:Vec<(u8, u8)> = vec![];
-						if let Some(pin) = self.pins.get_mut(pin_index as usize) {
+					// channel info into local data, through the analog mapping
+						let mut pin_updates:Vec<(u8, u16)> = vec![];
+						if let Some((pin_index, pin)) = self.pins.iter_mut().enumerate().find(|(_, pin)| pin.analog_channel == Some(channel)) {
+							let pin_index = pin_index as u8;
 							if pin.value != value {
 								pin_updates.push((pin_index, value));
 							}
 							pin.value = value;
+							self.callbacks.analog(pin_index, pin.value);
 						}
 	
 					if !pin_updates.is_empty() {
@@ -105,13 +191,14 @@ impl Board {
 							
 							if let Some(pin) = self.pins.get_mut(pin_index as usize) {
 								tracing::debug!("pin.mode: {}", pin.mode);
-								if pin.mode == PIN_MODE_INPUT || pin.mode == PIN_MODE_PULLUP {
+								if pin.mode == PinMode::Input || pin.mode == PinMode::Pullup {
 									let new_value = (value >> (index & 0x07)) & 0x01;
 									tracing::debug!("new_value: {new_value} pin.value: {}", pin.value);
-									if new_value != pin.value {
+									if u16::from(new_value) != pin.value {
 										pin_updates.push((pin_index, new_value != 0));
+										self.callbacks.digital_change(pin_index, new_value != 0);
 									}
-									pin.value = new_value;
+									pin.value = u16::from(new_value);
 								}
 							}
 						}
@@ -125,11 +212,13 @@ impl Board {
 				START_SYSEX => {
 					tracing::debug!("START_SYSEX");
 
-					let Some(end_index) = self.buffer.iter().position(|byte| byte == &END_SYSEX) else { break; };
-					let sysex_buffer = &self.buffer[0..=end_index];
-					tracing::debug!("{sysex_buffer:?}");
+					// the whole message is taken off the buffer before it is decoded, so that one too short for its
+					// command is skipped rather than holding up everything after it
+						let Some(end_index) = self.buffer.iter().position(|byte| byte == &END_SYSEX) else { break; };
+						let sysex_buffer = self.buffer.drain(0..=end_index).collect::<Vec<u8>>();
+						tracing::debug!("{sysex_buffer:?}");
 
-					let Some(byte_1) = sysex_buffer.get(1) else { break; };
+					let Some(byte_1) = sysex_buffer.get(1) else { tracing::warn!("skipping short sysex message: {sysex_buffer:?}"); continue; };
 
 					match *byte_1 {
 						END_SYSEX => {
@@ -139,22 +228,24 @@ impl Board {
 						ANALOG_MAPPING_RESPONSE => {
 							tracing::debug!("ANALOG_MAPPING_RESPONSE");
 
-							for index in 2..sysex_buffer.len() {
-								if sysex_buffer[index] == PIN_MODE_IGNORE {
+							for (pin_index, byte) in sysex_buffer.iter().enumerate().skip(2).map(|(index, byte)| (index - 2, byte)) {
+								if *byte == PIN_MODE_IGNORE {
 									continue;
 								}
 
-								if sysex_buffer[index] == END_SYSEX {
+								if *byte == END_SYSEX {
 									break;
 								}
 								
-								tracing::debug!("index: {index}, sysex_buffer[index]: {}, pin_index: {}", sysex_buffer[index], index-2);
+								tracing::debug!("byte: {byte}, pin_index: {pin_index}");
 
-								if let Some(pin) = self.pins.get_mut(index - 2) {
+								if let Some(pin) = self.pins.get_mut(pin_index) {
 									pin.analog = true;
+									pin.analog_channel = Some(*byte);
 								}
 							}
 
+							self.analog_mapping_received = true;
 							messages.push(Message::AnalogMappingResponse);
 						},
 						CAPABILITY_RESPONSE => {
@@ -167,29 +258,30 @@ impl Board {
 							self.pins.push(Pin::default_with_report_digital_active()); // 1 is unused.
 
 							let mut modes = vec![];
-							let mut resolution = None;
+							let mut resolutions = HashMap::new();
 
 							while index < sysex_buffer.len() - 1 {
 								// Completed a pin, push and continue.
 								if sysex_buffer[index] == 127u8 {
+									let mode = *modes.first().expect("pin mode");
 									self.pins.push(Pin {
 										analog: false,
-										mode: *modes.first().expect("pin mode"),
+										analog_channel: None,
+										mode,
 										modes: std::mem::take(&mut modes),
 										report_analog_active: false,
 										report_digital_active: false,
-										resolution: resolution.take().expect("pin resolution"),
+										resolution: resolutions[&mode],
+										resolutions: std::mem::take(&mut resolutions),
 										value: 0,
 									});
 									tracing::debug!("pin: {} {:?}", self.pins.len()-1, self.pins[self.pins.len()-1]);
 
 									index += 1;
 								} else {
-									modes.push(sysex_buffer[index]);
-									if resolution.is_none() {
-										// Only keep the first.
-										resolution.replace(sysex_buffer[index + 1]);
-									}
+									let mode = PinMode::try_from(sysex_buffer[index])?;
+									modes.push(mode);
+									resolutions.insert(mode, sysex_buffer[index + 1]);
 									index += 2;
 								}
 							}
@@ -199,8 +291,8 @@ impl Board {
 						REPORT_FIRMWARE => {
 							tracing::debug!("REPORT_FIRMWARE");
 
-							let Some(major) = sysex_buffer.get(2) else { break; };
-							let Some(minor) = sysex_buffer.get(3) else { break; };
+							let Some(major) = sysex_buffer.get(2) else { tracing::warn!("skipping short sysex message: {sysex_buffer:?}"); continue; };
+							let Some(minor) = sysex_buffer.get(3) else { tracing::warn!("skipping short sysex message: {sysex_buffer:?}"); continue; };
 							tracing::debug!("major: {major} minor: {minor}");
 
 							let firmware_version = format!("{major:o}.{minor:o}");
@@ -217,75 +309,310 @@ impl Board {
 						I2C_REPLY => {
 							tracing::debug!("I2C_REPLY");
 
-							let Some(byte_2) = sysex_buffer.get(2) else { break; };
-							let Some(byte_3) = sysex_buffer.get(3) else { break; };
-							let Some(byte_4) = sysex_buffer.get(4) else { break; };
-							let Some(byte_5) = sysex_buffer.get(5) else { break; };
-							let Some(byte_6) = sysex_buffer.get(6) else { break; };
-							let Some(byte_7) = sysex_buffer.get(7) else { break; };
-
-							let mut reply = I2CReply {
-								address: byte_2 | (u16::from(*byte_3) << 7) as u8,
-								register: byte_4 | (u16::from(*byte_5) << 7) as u8,
-								data: vec![byte_6 | (u16::from(*byte_7) << 7) as u8],
-							};
-
-							let mut index = 8;
-
-							while index < sysex_buffer.len() - 2 {
-								if sysex_buffer[index] == 0xF7 {
-									break;
-								}
-
-								if index + 2 > sysex_buffer.len() {
-									break;
+							let Some(byte_2) = sysex_buffer.get(2) else { tracing::warn!("skipping short sysex message: {sysex_buffer:?}"); continue; };
+							let Some(byte_3) = sysex_buffer.get(3) else { tracing::warn!("skipping short sysex message: {sysex_buffer:?}"); continue; };
+							let Some(byte_4) = sysex_buffer.get(4) else { tracing::warn!("skipping short sysex message: {sysex_buffer:?}"); continue; };
+							let byte_5 = sysex_buffer.get(5).filter(|byte| **byte != END_SYSEX).unwrap_or(&0);
+
+							// a device that does not respond is reported with no data at all, and a reply cut short
+							// after its register with none either
+								let reply = I2CReply {
+									address: u16::from(*byte_2) | u16::from(*byte_3) << 7,
+									register: byte_4 | (u16::from(*byte_5) << 7) as u8,
+									data: sysex_buffer.get(6..sysex_buffer.len() - 1)
+										.unwrap_or_default()
+										.chunks_exact(2)
+										.map(|pair| pair[0] | pair[1] << 7)
+										.collect(),
+								};
+
+							self.callbacks.i2c(&reply);
+
+							// subscribers get a copy of every reply, a blocking read waiting on it takes it ahead of
+							// storage, and anything left over is stored unless a subscriber received it
+								let mut delivered = false;
+								self.i2c_subscriptions.retain(|(address, register, sender)| {
+									if *address != reply.address || *register != reply.register {
+										return true;
+									}
+									let sent = sender.send(reply.clone()).is_ok();
+									delivered |= sent;
+									sent
+								});
+
+								let reply = self.i2c_waiters.iter_mut().try_fold(reply, |reply, waiter| waiter.offer(reply));
+								if let Some(reply) = reply.filter(|_| !delivered) {
+									self.i2c_data.entry(reply.address).or_default().push(reply, self.i2c_data_limit);
 								}
 
-								reply.data.push(sysex_buffer[index] | sysex_buffer[index + 1] << 7);
-
-								index += 2;
-							}
-
-							self.i2c_data.push(reply);
-
 							messages.push(Message::I2CReply);
 						},
 						PIN_STATE_RESPONSE => {
 							tracing::debug!("PIN_STATE_RESPONSE");
 
-							let Some(byte_2) = sysex_buffer.get(2) else { break; };
-							let Some(byte_3) = sysex_buffer.get(3) else { break; };
+							let Some(byte_2) = sysex_buffer.get(2) else { tracing::warn!("skipping short sysex message: {sysex_buffer:?}"); continue; };
+							let Some(byte_3) = sysex_buffer.get(3) else { tracing::warn!("skipping short sysex message: {sysex_buffer:?}"); continue; };
 
 							if byte_3 == &END_SYSEX {
-								messages.push(Message::PinStateResponse);
-								continue;
+								messages.push(Message::PinStateResponse { pin: None });
+							} else {
+								let pin_index = *byte_2;
+								let Some(pin) = self.pins.get_mut(pin_index as usize) else {
+									return Err(Error::PinOutOfBounds { pin: pin_index, len: self.pins.len(), source: "poll : PIN_STATE_RESPONSE".to_string() })
+								};
+
+								pin.set_mode(PinMode::try_from(*byte_3)?);
+
+								// the value is sent 7 bits at a time, least significant first, in as many bytes as it needs,
+								// so it is saturated rather than truncated when it is wider than the pin's value
+									let value = sysex_buffer[4..sysex_buffer.len() - 1]
+										.iter()
+										.rev()
+										.fold(0u32, |value, byte| value.checked_mul(0x80).map_or(u32::MAX, |value| value | u32::from(*byte)));
+									pin.value = u16::try_from(value).unwrap_or(u16::MAX);
+
+								messages.push(Message::PinStateResponse { pin: Some(pin_index) });
+							}
+						},
+						ACCELSTEPPER_DATA => {
+							tracing::debug!("ACCELSTEPPER_DATA");
+
+							let Some(command) = sysex_buffer.get(2) else { tracing::warn!("skipping short sysex message: {sysex_buffer:?}"); continue; };
+							let Some(device) = sysex_buffer.get(3) else { tracing::warn!("skipping short sysex message: {sysex_buffer:?}"); continue; };
+
+							match *command {
+								ACCELSTEPPER_REPORT_POSITION | ACCELSTEPPER_MOVE_COMPLETE => {
+									let Some(position) = sysex_buffer[..end_index].get(4..9) else { tracing::warn!("skipping short sysex message: {sysex_buffer:?}"); continue; };
+									let position = decode_i32(position.try_into().expect("five position bytes"));
+									tracing::debug!("device: {device} position: {position}");
+
+									if *command == ACCELSTEPPER_REPORT_POSITION {
+										messages.push(Message::StepperPosition { device: *device, position });
+									} else {
+										messages.push(Message::StepperMoveComplete { device: *device, position });
+									}
+								},
+								MULTISTEPPER_MOVE_COMPLETE => {
+									messages.push(Message::MultiStepperMoveComplete { group: *device });
+								},
+								_ => {
+									tracing::debug!("unknown stepper command: {command}");
+								},
+							}
+						},
+						ENCODER_DATA => {
+							tracing::debug!("ENCODER_DATA");
+
+							for report in sysex_buffer[2..sysex_buffer.len() - 1].chunks_exact(5) {
+								let id = report[0] & ENCODER_CHANNEL_MASK;
+								let magnitude = i32::from(report[1])
+									| i32::from(report[2]) << 7
+									| i32::from(report[3]) << 14
+									| i32::from(report[4]) << 21;
+								let position = if report[0] & ENCODER_DIRECTION_MASK == 0 { magnitude } else { -magnitude };
+								tracing::debug!("id: {id} position: {position}");
+
+								self.encoder_positions.insert(id, position);
+								messages.push(Message::Encoder { id, position });
+							}
+						},
+						ONEWIRE_DATA => {
+							tracing::debug!("ONEWIRE_DATA");
+
+							let Some(command) = sysex_buffer.get(2) else { tracing::warn!("skipping short sysex message: {sysex_buffer:?}"); continue; };
+							let Some(pin) = sysex_buffer.get(3) else { tracing::warn!("skipping short sysex message: {sysex_buffer:?}"); continue; };
+							let data = decode_7bit(sysex_buffer.get(4..sysex_buffer.len() - 1).unwrap_or_default());
+
+							match *command {
+								ONEWIRE_SEARCH_REPLY | ONEWIRE_SEARCH_ALARMS_REPLY => {
+									let addresses = data
+										.chunks_exact(8)
+										.map(|address| u64::from_le_bytes(address.try_into().expect("eight address bytes")))
+										.collect::<Vec<u64>>();
+									tracing::debug!("pin: {pin} addresses: {addresses:X?}");
+
+									if *command == ONEWIRE_SEARCH_REPLY {
+										messages.push(Message::OneWireSearchReply { pin: *pin, addresses });
+									} else {
+										messages.push(Message::OneWireSearchAlarmsReply { pin: *pin, addresses });
+									}
+								},
+								ONEWIRE_READ_REPLY => {
+									let Some(correlation_id) = data.get(0..2) else { tracing::warn!("skipping short sysex message: {sysex_buffer:?}"); continue; };
+									let correlation_id = u16::from_le_bytes([correlation_id[0], correlation_id[1]]);
+									tracing::debug!("pin: {pin} correlation_id: {correlation_id} data: {:?}", &data[2..]);
+
+									messages.push(Message::OneWireReadReply { pin: *pin, correlation_id, data: data[2..].to_vec() });
+								},
+								_ => {
+									tracing::debug!("unknown onewire command: {command}");
+								},
 							}
+						},
+						SERIAL_DATA => {
+							tracing::debug!("SERIAL_DATA");
+
+							let Some(command) = sysex_buffer.get(2) else { tracing::warn!("skipping short sysex message: {sysex_buffer:?}"); continue; };
 
-							let pin = *byte_2;
-							let Some(pin) = self.pins.get_mut(pin as usize) else {
-								return Err(Error::PinOutOfBounds { pin, len: self.pins.len(), source: "poll : PIN_STATE_RESPONSE".to_string() })
-							};
+							if command & SERIAL_COMMAND_MASK == SERIAL_REPLY {
+								let port = SerialPortId::from_byte(*command);
+								let data = sysex_buffer[3..sysex_buffer.len() - 1]
+									.chunks_exact(2)
+									.map(|pair| pair[0] | pair[1] << 7)
+									.collect::<Vec<u8>>();
+								tracing::debug!("port: {port:?} data: {data:?}");
 
-							pin.mode = *byte_3;
+								if let Some(buffer) = self.serial_data.get_mut(&port) {
+									buffer.extend(&data);
+								}
+								messages.push(Message::SerialReply { port, data });
+							} else {
+								tracing::debug!("unknown serial command: {command}");
+							}
+						},
+						SCHEDULER_DATA => {
+							tracing::debug!("SCHEDULER_DATA");
+
+							let Some(command) = sysex_buffer.get(2) else { tracing::warn!("skipping short sysex message: {sysex_buffer:?}"); continue; };
+
+							match *command {
+								QUERY_ALL_TASKS_REPLY => {
+									let ids = sysex_buffer[3..sysex_buffer.len() - 1].to_vec();
+									tracing::debug!("ids: {ids:?}");
+									messages.push(Message::SchedulerTasks(ids));
+								},
+								QUERY_TASK_REPLY | ERROR_TASK_REPLY => {
+									let Some(id) = sysex_buffer[..end_index].get(3) else { tracing::warn!("skipping short sysex message: {sysex_buffer:?}"); continue; };
+									let payload = decode_7bit(sysex_buffer.get(4..sysex_buffer.len() - 1).unwrap_or_default());
+									let task = SchedulerTask::from_reply(*id, &payload);
+									tracing::debug!("task: {task:?}");
+
+									if *command == QUERY_TASK_REPLY {
+										messages.push(Message::SchedulerTaskReply { id: *id, task });
+									} else {
+										messages.push(Message::SchedulerTaskError(task.unwrap_or(SchedulerTask { id: *id, ..Default::default() })));
+									}
+								},
+								_ => {
+									tracing::debug!("unknown scheduler command: {command}");
+								},
+							}
+						},
+						SHIFT_DATA => {
+							tracing::debug!("SHIFT_DATA");
+
+							let Some(command) = sysex_buffer.get(2) else { tracing::warn!("skipping short sysex message: {sysex_buffer:?}"); continue; };
+							let Some(data_pin) = sysex_buffer.get(3) else { tracing::warn!("skipping short sysex message: {sysex_buffer:?}"); continue; };
+
+							if *command == SHIFT_IN_REPLY {
+								let data = sysex_buffer.get(4..sysex_buffer.len() - 1)
+									.unwrap_or_default()
+									.chunks_exact(2)
+									.map(|pair| pair[0] | pair[1] << 7)
+									.collect::<Vec<u8>>();
+								tracing::debug!("data_pin: {data_pin} data: {data:?}");
+
+								messages.push(Message::ShiftInReply { data_pin: *data_pin, data });
+							} else {
+								tracing::debug!("unknown shift command: {command}");
+							}
+						},
+						STRING_DATA => {
+							tracing::debug!("STRING_DATA");
 
-							// TODO: Extended values.
-							let Some(byte_4) = sysex_buffer.get(4) else { break; };
-							pin.value = *byte_4;
+							let string = sysex_buffer[2..sysex_buffer.len() - 1]
+								.chunks_exact(2)
+								.map(|pair| char::from(pair[0] | pair[1] << 7))
+								.collect::<String>();
+							tracing::debug!("string: {string}");
 
-							messages.push(Message::PinStateResponse);
+							messages.push(Message::StringData(string));
 						},
 						_ => {
 							tracing::debug!("UnknownSysEx");
 							return Err(Error::UnknownSysEx { code: *byte_1 });
 						},
 					}
-
-					self.buffer.drain(0..sysex_buffer.len());
 				},
 				_ => { return Err(Error::BadByte( self.buffer.remove(0))); },
 			}
 		}
 
+		for message in &messages {
+			self.callbacks.message(message);
+		}
+
 		Ok(messages)
 	}
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::constants::{
+		ACCELSTEPPER_DATA,
+		ACCELSTEPPER_REPORT_POSITION,
+		END_SYSEX,
+		I2C_REPLY,
+		ONEWIRE_DATA,
+		ONEWIRE_READ_REPLY,
+		QUERY_TASK_REPLY,
+		SCHEDULER_DATA,
+		START_SYSEX,
+		STRING_DATA
+	};
+	use crate::types::{
+		I2CReply,
+		Message,
+	};
+
+	use super::Board;
+
+	#[test]
+	fn short_i2c_reply_has_no_data() {
+		let (mut board, incoming, _outgoing) = Board::detached();
+		incoming.send(vec![START_SYSEX, I2C_REPLY, 0x48, 0x00, 0x05, END_SYSEX]).unwrap();
+
+		assert_eq!(board.poll().unwrap(), vec![Message::I2CReply]);
+		assert_eq!(board.take_i2c_reply(0x48), Some(I2CReply { address: 0x48, register: 0x05, data: vec![] }));
+	}
+
+	/// Feed `bytes` to a fresh board and poll it twice, returning the messages from each poll.
+	fn poll_twice(bytes: Vec<u8>) -> (Vec<Message>, Vec<Message>) {
+		let (mut board, incoming, _outgoing) = Board::detached();
+		incoming.send(bytes).unwrap();
+		(board.poll().unwrap(), board.poll().unwrap())
+	}
+
+	#[test]
+	fn short_stepper_report_is_skipped() {
+		let (first, second) = poll_twice(vec![
+			START_SYSEX, ACCELSTEPPER_DATA, ACCELSTEPPER_REPORT_POSITION, 0x00, 0x01, 0x02, END_SYSEX,
+			START_SYSEX, STRING_DATA, b'h', 0x00, END_SYSEX,
+		]);
+
+		assert_eq!(first, vec![Message::StringData("h".to_string())]);
+		assert_eq!(second, vec![]);
+	}
+
+	#[test]
+	fn short_onewire_read_reply_is_skipped() {
+		let (first, second) = poll_twice(vec![
+			START_SYSEX, ONEWIRE_DATA, ONEWIRE_READ_REPLY, 0x02, 0x01, END_SYSEX,
+			START_SYSEX, STRING_DATA, b'h', 0x00, END_SYSEX,
+		]);
+
+		assert_eq!(first, vec![Message::StringData("h".to_string())]);
+		assert_eq!(second, vec![]);
+	}
+
+	#[test]
+	fn short_scheduler_task_reply_is_skipped() {
+		let (first, second) = poll_twice(vec![
+			START_SYSEX, SCHEDULER_DATA, QUERY_TASK_REPLY, END_SYSEX,
+			START_SYSEX, STRING_DATA, b'h', 0x00, END_SYSEX,
+		]);
+
+		assert_eq!(first, vec![Message::StringData("h".to_string())]);
+		assert_eq!(second, vec![]);
+	}
+}
@@ -0,0 +1,134 @@
+#![allow(clippy::cast_possible_truncation)]
+
+use std::time::Duration;
+
+use crate::constants::{
+    ADD_TO_FIRMATA_TASK,
+    CREATE_FIRMATA_TASK,
+    DELAY_FIRMATA_TASK,
+    DELETE_FIRMATA_TASK,
+    END_SYSEX,
+    FIRMATA_TASK_CHUNK_SIZE,
+    QUERY_ALL_FIRMATA_TASKS,
+    QUERY_FIRMATA_TASK,
+    RESET_FIRMATA_TASKS,
+    SCHEDULE_FIRMATA_TASK,
+    SCHEDULER_DATA,
+    START_SYSEX,
+    SYSEX_REALTIME
+};
+use crate::encoding::encode_7bit;
+use crate::types::{
+    Message,
+    Result,
+    SchedulerTask,
+};
+
+use super::Board;
+
+// recording
+	impl Board {
+		/// Run `commands` against the board with everything they would send captured instead, and return
+		/// the captured bytes for use as task data with [`Board::scheduler_create_task`].
+		///
+		/// The board's local pin state is updated as though the commands had been sent.
+		pub fn record_task<F>(&mut self, commands: F) -> Result<Vec<u8>>
+		where
+			F: FnOnce(&mut Board) -> Result<()>,
+		{
+			let outer_recording = self.task_recording.replace(vec![]);
+			let result = commands(self);
+			let recording = std::mem::replace(&mut self.task_recording, outer_recording).unwrap_or_default();
+
+			result.map(|()| recording)
+		}
+	}
+
+// scheduler
+	impl Board {
+		/// Pause the running task for `delay_ms` milliseconds. This is only meaningful while recording a task.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn scheduler_delay_task(&mut self, delay_ms: u32) -> Result<()> {
+			let mut buf = vec![START_SYSEX, SCHEDULER_DATA, DELAY_FIRMATA_TASK];
+			buf.extend(encode_7bit(&delay_ms.to_le_bytes()));
+			buf.push(END_SYSEX);
+
+			self.write_to_connection(&buf)
+		}
+
+		/// Create task `id` on the board holding `data`, as produced by [`Board::record_task`].
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn scheduler_create_task(&mut self, id: u8, data: &[u8]) -> Result<()> {
+			let length = data.len() as u16;
+			self.write_to_connection(&[
+				START_SYSEX,
+				SCHEDULER_DATA,
+				CREATE_FIRMATA_TASK,
+				id,
+				length as u8 & SYSEX_REALTIME,
+				(length >> 7) as u8 & SYSEX_REALTIME,
+				END_SYSEX,
+			])?;
+
+			for chunk in data.chunks(FIRMATA_TASK_CHUNK_SIZE) {
+				self.scheduler_add_to_task(id, chunk)?;
+			}
+
+			Ok(())
+		}
+
+		/// Append `data` to task `id`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn scheduler_add_to_task(&mut self, id: u8, data: &[u8]) -> Result<()> {
+			let mut buf = vec![START_SYSEX, SCHEDULER_DATA, ADD_TO_FIRMATA_TASK, id];
+			buf.extend(encode_7bit(data));
+			buf.push(END_SYSEX);
+
+			self.write_to_connection(&buf)
+		}
+
+		/// Run task `id` once `delay_ms` milliseconds have passed. A task can repeat itself by scheduling
+		/// its own id as its final command.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn scheduler_schedule_task(&mut self, id: u8, delay_ms: u32) -> Result<()> {
+			let mut buf = vec![START_SYSEX, SCHEDULER_DATA, SCHEDULE_FIRMATA_TASK, id];
+			buf.extend(encode_7bit(&delay_ms.to_le_bytes()));
+			buf.push(END_SYSEX);
+
+			self.write_to_connection(&buf)
+		}
+
+		/// Delete task `id` from the board.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn scheduler_delete_task(&mut self, id: u8) -> Result<()> {
+			self.write_to_connection(&[START_SYSEX, SCHEDULER_DATA, DELETE_FIRMATA_TASK, id, END_SYSEX])
+		}
+
+		/// Delete every task from the board.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn scheduler_reset_tasks(&mut self) -> Result<()> {
+			self.write_to_connection(&[START_SYSEX, SCHEDULER_DATA, RESET_FIRMATA_TASKS, END_SYSEX])
+		}
+
+		/// Ask the board for the ids of all of its tasks.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn scheduler_query_all_tasks(&mut self, timeout: Duration) -> Result<Vec<u8>> {
+			self.write_to_connection(&[START_SYSEX, SCHEDULER_DATA, QUERY_ALL_FIRMATA_TASKS, END_SYSEX])?;
+
+			self.poll_until(timeout, "scheduler_query_all_tasks", |_, message| match message {
+				Message::SchedulerTasks(ids) => Some(ids.clone()),
+				_ => None,
+			})
+		}
+
+		/// Ask the board for the state of task `id`, which is `None` when there is no such task.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn scheduler_query_task(&mut self, id: u8, timeout: Duration) -> Result<Option<SchedulerTask>> {
+			self.write_to_connection(&[START_SYSEX, SCHEDULER_DATA, QUERY_FIRMATA_TASK, id, END_SYSEX])?;
+
+			self.poll_until(timeout, "scheduler_query_task", |_, message| match message {
+				Message::SchedulerTaskReply { id: reply_id, task } if *reply_id == id => Some(task.clone()),
+				_ => None,
+			})
+		}
+	}
@@ -0,0 +1,263 @@
+#![allow(clippy::cast_possible_truncation)]
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::constants::{
+    END_SYSEX,
+    SERIAL_CLOSE,
+    SERIAL_CONFIG,
+    SERIAL_DATA,
+    SERIAL_FLUSH,
+    SERIAL_LISTEN,
+    SERIAL_READ,
+    SERIAL_READ_CONTINUOUSLY,
+    SERIAL_STOP_READING,
+    SERIAL_WRITE,
+    SERIAL_WRITE_CHUNK_SIZE,
+    START_SYSEX,
+    SYSEX_REALTIME
+};
+use crate::types::{
+    Error,
+    Message,
+    PinMode,
+    PinRef,
+    Result,
+    SerialPortId,
+};
+
+use super::Board;
+
+// serial
+	impl Board {
+		/// Open serial port `id` at `baud`. Software serial ports also need their `rx` and `tx` pins,
+		/// which are placed in `PinMode::Serial`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn serial_config(&mut self, id: SerialPortId, baud: u32, rx: Option<&dyn PinRef>, tx: Option<&dyn PinRef>) -> Result<()> {
+			let pins = [rx, tx].into_iter().flatten().map(|pin| pin.resolve(self)).collect::<Result<Vec<u8>>>()?;
+
+			for pin in &pins {
+				let Some(p) = self.pins.get(*pin as usize) else {
+					return Err(Error::PinOutOfBounds { pin: *pin, len: self.pins.len(), source: "serial_config".to_string() })
+				};
+				if !p.modes.contains(&PinMode::Serial) {
+					return Err(Error::InvalidPinMode { pin: *pin, mode: PinMode::Serial, modes: p.modes.clone() });
+				}
+			}
+			for pin in &pins {
+				self.pins[*pin as usize].set_mode(PinMode::Serial);
+			}
+
+			let mut buf = vec![
+				START_SYSEX,
+				SERIAL_DATA,
+				SERIAL_CONFIG | id.as_byte(),
+				baud as u8 & SYSEX_REALTIME,
+				(baud >> 7) as u8 & SYSEX_REALTIME,
+				(baud >> 14) as u8 & SYSEX_REALTIME,
+			];
+			buf.extend(pins);
+			buf.push(END_SYSEX);
+
+			self.write_to_connection(&buf)
+		}
+
+		/// Write `data` out of serial port `id`, split across as many messages as the firmware needs.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn serial_write(&mut self, id: SerialPortId, data: &[u8]) -> Result<()> {
+			for chunk in data.chunks(SERIAL_WRITE_CHUNK_SIZE) {
+				let mut buf = vec![START_SYSEX, SERIAL_DATA, SERIAL_WRITE | id.as_byte()];
+
+				for datum in chunk {
+					buf.push(datum & SYSEX_REALTIME);
+					buf.push(datum >> 7);
+				}
+
+				buf.push(END_SYSEX);
+
+				self.write_to_connection(&buf)?;
+			}
+
+			Ok(())
+		}
+
+		/// Start reporting data received on serial port `id`, at most `max_bytes` per report, or
+		/// everything available when `max_bytes` is 0. The data arrives as `Message::SerialReply`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn serial_read_continuously(&mut self, id: SerialPortId, max_bytes: u16) -> Result<()> {
+			let mut buf = vec![START_SYSEX, SERIAL_DATA, SERIAL_READ | id.as_byte(), SERIAL_READ_CONTINUOUSLY];
+
+			if max_bytes > 0 {
+				buf.push(max_bytes as u8 & SYSEX_REALTIME);
+				buf.push((max_bytes >> 7) as u8 & SYSEX_REALTIME);
+			}
+
+			buf.push(END_SYSEX);
+
+			self.write_to_connection(&buf)
+		}
+
+		/// Stop reporting data received on serial port `id`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn serial_stop_reading(&mut self, id: SerialPortId) -> Result<()> {
+			self.write_to_connection(&[START_SYSEX, SERIAL_DATA, SERIAL_READ | id.as_byte(), SERIAL_STOP_READING, END_SYSEX])
+		}
+
+		/// Close serial port `id`, dropping any received data that has not been read.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn serial_close(&mut self, id: SerialPortId) -> Result<()> {
+			self.serial_data.remove(&id);
+			self.write_to_connection(&[START_SYSEX, SERIAL_DATA, SERIAL_CLOSE | id.as_byte(), END_SYSEX])
+		}
+
+		/// Wait for the board to finish transmitting on serial port `id`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn serial_flush(&mut self, id: SerialPortId) -> Result<()> {
+			self.write_to_connection(&[START_SYSEX, SERIAL_DATA, SERIAL_FLUSH | id.as_byte(), END_SYSEX])
+		}
+
+		/// Make software serial port `id` the one that listens, as only one can receive at a time.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn serial_listen(&mut self, id: SerialPortId) -> Result<()> {
+			self.write_to_connection(&[START_SYSEX, SERIAL_DATA, SERIAL_LISTEN | id.as_byte(), END_SYSEX])
+		}
+
+		/// Open serial port `id` at `baud` and start reading from it, returning a stream that
+		/// implements `std::io::Read` and `std::io::Write`. Software serial ports also need their `rx`
+		/// and `tx` pins.
+		#[tracing::instrument(skip(self), level = "DEBUG")]
+		pub fn serial_port(&mut self, id: SerialPortId, baud: u32, rx: Option<&dyn PinRef>, tx: Option<&dyn PinRef>) -> Result<SerialStream<'_>> {
+			self.serial_config(id, baud, rx, tx)?;
+			self.serial_read_continuously(id, 0)?;
+			self.serial_data.insert(id, VecDeque::new());
+
+			Ok(SerialStream { board: self, id, timeout: SerialStream::DEFAULT_TIMEOUT })
+		}
+	}
+
+/// A serial port on the board, opened with [`Board::serial_port`].
+///
+/// Reads block until at least one byte has arrived, or fail with `std::io::ErrorKind::TimedOut`.
+#[derive(Debug)]
+pub struct SerialStream<'a> {
+	board: &'a mut Board,
+	id: SerialPortId,
+	timeout: Duration,
+}
+
+// get and set
+	impl SerialStream<'_> {
+		/// Default time a read waits for data.
+		pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(1);
+
+		#[must_use]
+		/// Get the id of the port.
+		pub fn id(&self) -> SerialPortId {
+			self.id
+		}
+
+		#[must_use]
+		/// Get the time a read waits for data.
+		pub fn timeout(&self) -> Duration {
+			self.timeout
+		}
+
+		/// Set the time a read waits for data.
+		pub fn set_timeout(&mut self, timeout: Duration) {
+			self.timeout = timeout;
+		}
+
+		/// Get the board the port belongs to.
+		pub fn board(&mut self) -> &mut Board {
+			self.board
+		}
+	}
+
+// control
+	impl SerialStream<'_> {
+		/// Make this software serial port the one that listens.
+		pub fn listen(&mut self) -> Result<()> {
+			self.board.serial_listen(self.id)
+		}
+
+		/// Stop reading and close the port.
+		pub fn close(self) -> Result<()> {
+			self.board.serial_stop_reading(self.id)?;
+			self.board.serial_close(self.id)
+		}
+	}
+
+impl std::io::Read for SerialStream<'_> {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		if buf.is_empty() {
+			return Ok(0);
+		}
+
+		let id = self.id;
+		let has_data = |board: &Board| board.serial_data.get(&id).is_some_and(|data| !data.is_empty());
+
+		if !has_data(self.board) {
+			self.board.poll_until(self.timeout, "serial_read", |board, message| match message {
+				Message::SerialReply { port, .. } if *port == id && has_data(board) => Some(()),
+				_ => None,
+			})?;
+		}
+
+		let Some(data) = self.board.serial_data.get_mut(&id) else {
+			return Err(Error::Disconnected.into());
+		};
+
+		let len = buf.len().min(data.len());
+		for (slot, byte) in buf.iter_mut().zip(data.drain(..len)) {
+			*slot = byte;
+		}
+
+		Ok(len)
+	}
+}
+
+impl std::io::Write for SerialStream<'_> {
+	fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+		self.board.serial_write(self.id, buf)?;
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		self.board.serial_flush(self.id)?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::constants::{
+		END_SYSEX,
+		SERIAL_DATA,
+		SERIAL_WRITE,
+		START_SYSEX
+	};
+	use crate::types::SerialPortId;
+
+	use super::Board;
+
+	#[test]
+	fn serial_write_splits_long_data_to_fit_the_firmware_buffer() {
+		let (mut board, _incoming, outgoing) = Board::detached();
+		let data = (0..100).collect::<Vec<u8>>();
+
+		board.serial_write(SerialPortId::Hardware(1), &data).unwrap();
+
+		let messages = outgoing.try_iter().collect::<Vec<Vec<u8>>>();
+		assert_eq!(messages.len(), 4);
+		let mut sent = vec![];
+		for message in messages {
+			// the firmware keeps everything after START_SYSEX in a 64 byte buffer
+				assert!(message.len() - 1 <= 64, "{} bytes", message.len());
+			assert_eq!(message[..3], [START_SYSEX, SERIAL_DATA, SERIAL_WRITE | 0x01]);
+			assert_eq!(message.last(), Some(&END_SYSEX));
+			sent.extend(message[3..message.len() - 1].chunks_exact(2).map(|pair| pair[0] | pair[1] << 7));
+		}
+		assert_eq!(sent, data);
+	}
+}
@@ -0,0 +1,121 @@
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_sign_loss)]
+
+use crate::constants::{
+    END_SYSEX,
+    SERVO_CONFIG,
+    START_SYSEX,
+    SYSEX_REALTIME
+};
+use crate::types::{
+    Error,
+    PinMode,
+    PinRef,
+    Result,
+};
+
+use super::Board;
+
+// servo
+	impl Board {
+		/// Attach a servo to `pin`, with its pulse range set from `min_pulse` to `max_pulse` microseconds.
+		/// This also places the pin in `PinMode::Servo`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn servo_config(&mut self, pin: impl PinRef, min_pulse: u16, max_pulse: u16) -> Result<()> {
+			let pin = pin.resolve(self)?;
+
+			if let Some(p) = self.pins.get_mut(pin as usize) {
+				if !p.modes.contains(&PinMode::Servo) {
+					return Err(Error::InvalidPinMode { pin, mode: PinMode::Servo, modes: p.modes.clone() });
+				}
+
+				p.set_mode(PinMode::Servo);
+			} else {
+				return Err(Error::PinOutOfBounds { pin, len: self.pins.len(), source: "servo_config".to_string() })
+			}
+
+			self.write_to_connection(&[
+				START_SYSEX,
+				SERVO_CONFIG,
+				pin,
+				min_pulse as u8 & SYSEX_REALTIME,
+				(min_pulse >> 7) as u8 & SYSEX_REALTIME,
+				max_pulse as u8 & SYSEX_REALTIME,
+				(max_pulse >> 7) as u8 & SYSEX_REALTIME,
+				END_SYSEX,
+			])
+		}
+	}
+
+/// A servo attached to a pin of a [`Board`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Servo {
+	pin: u8,
+	min_pulse: u16,
+	max_pulse: u16,
+}
+
+// creation
+	impl Servo {
+		/// Default minimum pulse width in microseconds, as used by the Arduino Servo library.
+		pub const DEFAULT_MIN_PULSE: u16 = 544;
+		/// Default maximum pulse width in microseconds, as used by the Arduino Servo library.
+		pub const DEFAULT_MAX_PULSE: u16 = 2400;
+		/// Shortest pulse in microseconds that can be written as a pulse, as the firmware takes any smaller
+		/// value to be an angle.
+		pub const MIN_WRITE_PULSE: u16 = 544;
+
+		/// Configure `pin` on the `board` as a servo with a pulse range of `min_pulse` to `max_pulse`
+		/// microseconds.
+		pub fn attach(board: &mut Board, pin: impl PinRef, min_pulse: u16, max_pulse: u16) -> Result<Servo> {
+			let pin = pin.resolve(board)?;
+
+			let (min_pulse, max_pulse) = if min_pulse <= max_pulse {
+				(min_pulse, max_pulse)
+			} else {
+				(max_pulse, min_pulse)
+			};
+
+			board.servo_config(pin, min_pulse, max_pulse)?;
+
+			Ok(Servo { pin, min_pulse, max_pulse })
+		}
+	}
+
+// get
+	impl Servo {
+		#[must_use]
+		/// Get the pin the servo is attached to.
+		pub fn pin(&self) -> u8 {
+			self.pin
+		}
+
+		#[must_use]
+		/// Get the configured pulse range in microseconds.
+		pub fn pulse_range(&self) -> (u16, u16) {
+			(self.min_pulse, self.max_pulse)
+		}
+	}
+
+// write
+	impl Servo {
+		/// Move the servo to `degrees`, clamped to between 0 and 180 and rounded to a whole degree. The
+		/// firmware maps the angle onto the pulse range the servo was attached with.
+		pub fn write_degrees(&self, board: &mut Board, degrees: f32) -> Result<()> {
+			board.write_analog_value(self.pin, degrees.clamp(0.0, 180.0).round() as u16)
+		}
+
+		/// Drive the servo with a pulse of `micros` microseconds, clamped to the configured pulse range.
+		///
+		/// Firmata treats values below `MIN_WRITE_PULSE` as an angle, so a pulse shorter than that is
+		/// refused with `Error::ServoPulseTooShort`. Such pulses can still be reached with
+		/// [`Servo::write_degrees`] when the pulse range extends below it.
+		pub fn write_micros(&self, board: &mut Board, micros: u16) -> Result<()> {
+			let micros = micros.clamp(self.min_pulse, self.max_pulse);
+			if micros < Servo::MIN_WRITE_PULSE {
+				return Err(Error::ServoPulseTooShort { pin: self.pin, micros, min: Servo::MIN_WRITE_PULSE });
+			}
+
+			board.write_analog_value(self.pin, micros)
+		}
+	}
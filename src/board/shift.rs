@@ -0,0 +1,180 @@
+use std::time::Duration;
+
+use crate::constants::{
+    END_SYSEX,
+    SHIFT_DATA,
+    SHIFT_IN,
+    SHIFT_OUT,
+    START_SYSEX,
+    SYSEX_REALTIME
+};
+use crate::types::{
+    BitOrder,
+    Error,
+    Message,
+    PinMode,
+    PinRef,
+    Result,
+};
+
+use super::Board;
+
+// shift
+	impl Board {
+		/// Place the `data_pin` and `clock_pin` of a shift register in `PinMode::Shift`.
+		fn shift_pins(&mut self, data_pin: u8, clock_pin: u8, source: &str) -> Result<()> {
+			for pin in [data_pin, clock_pin] {
+				let Some(p) = self.pins.get(pin as usize) else {
+					return Err(Error::PinOutOfBounds { pin, len: self.pins.len(), source: source.to_string() })
+				};
+				if !p.modes.contains(&PinMode::Shift) {
+					return Err(Error::InvalidPinMode { pin, mode: PinMode::Shift, modes: p.modes.clone() });
+				}
+			}
+			for pin in [data_pin, clock_pin] {
+				self.pins[pin as usize].set_mode(PinMode::Shift);
+			}
+
+			Ok(())
+		}
+
+		/// Clock `data` out of `data_pin` on `clock_pin`, as with Arduino's `shiftOut`. Latching the
+		/// data into the register's outputs is left to the caller.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn shift_out(&mut self, data_pin: impl PinRef, clock_pin: impl PinRef, bit_order: BitOrder, data: &[u8]) -> Result<()> {
+			let data_pin = data_pin.resolve(self)?;
+			let clock_pin = clock_pin.resolve(self)?;
+
+			self.shift_pins(data_pin, clock_pin, "shift_out")?;
+
+			let mut buf = vec![START_SYSEX, SHIFT_DATA, SHIFT_OUT, data_pin, clock_pin, bit_order.as_byte()];
+
+			for datum in data {
+				buf.push(datum & SYSEX_REALTIME);
+				buf.push(datum >> 7);
+			}
+
+			buf.push(END_SYSEX);
+
+			self.write_to_connection(&buf)
+		}
+
+		/// Clock `len` bytes into `data_pin` on `clock_pin`, as with Arduino's `shiftIn`, and wait up to
+		/// `timeout` for them. Loading the register's inputs beforehand is left to the caller.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn shift_in(&mut self, data_pin: impl PinRef, clock_pin: impl PinRef, bit_order: BitOrder, len: u8, timeout: Duration) -> Result<Vec<u8>> {
+			let data_pin = data_pin.resolve(self)?;
+			let clock_pin = clock_pin.resolve(self)?;
+
+			self.shift_pins(data_pin, clock_pin, "shift_in")?;
+
+			self.write_to_connection(&[
+				START_SYSEX,
+				SHIFT_DATA,
+				SHIFT_IN,
+				data_pin,
+				clock_pin,
+				bit_order.as_byte(),
+				len & SYSEX_REALTIME,
+				END_SYSEX,
+			])?;
+
+			self.poll_until(timeout, "shift_in", |_, message| match message {
+				Message::ShiftInReply { data_pin: reply_pin, data } if *reply_pin == data_pin => Some(data.clone()),
+				_ => None,
+			})
+		}
+	}
+
+/// A chain of 74HC595 shift registers, whose outputs are driven like extra digital pins.
+///
+/// Output 0 is the first output of the register wired to the board, and output 8 the first output of
+/// the next register along the chain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShiftRegisterChain {
+	data_pin: u8,
+	clock_pin: u8,
+	latch_pin: u8,
+	state: Vec<u8>,
+}
+
+// creation
+	impl ShiftRegisterChain {
+		/// Set up a chain of `registers` registers, with every output low.
+		pub fn new(board: &mut Board, data_pin: impl PinRef, clock_pin: impl PinRef, latch_pin: impl PinRef, registers: usize) -> Result<Self> {
+			let data_pin = data_pin.resolve(board)?;
+			let clock_pin = clock_pin.resolve(board)?;
+			let latch_pin = latch_pin.resolve(board)?;
+
+			board.set_pin_mode(latch_pin, PinMode::Output)?;
+
+			let chain = Self { data_pin, clock_pin, latch_pin, state: vec![0; registers] };
+			chain.update(board)?;
+
+			Ok(chain)
+		}
+	}
+
+// get
+	impl ShiftRegisterChain {
+		#[must_use]
+		/// Get the number of outputs in the chain.
+		pub fn len(&self) -> usize {
+			self.state.len() * 8
+		}
+
+		#[must_use]
+		/// Check if the chain has no registers.
+		pub fn is_empty(&self) -> bool {
+			self.state.is_empty()
+		}
+
+		#[must_use]
+		/// Get the level last written to `output`.
+		pub fn get(&self, output: usize) -> Option<bool> {
+			self.state.get(output / 8).map(|byte| byte & (1 << (output % 8)) != 0)
+		}
+
+		#[must_use]
+		/// Get the levels of every output, one byte per register starting with the one wired to the board.
+		pub fn bytes(&self) -> &[u8] {
+			&self.state
+		}
+	}
+
+// write
+	impl ShiftRegisterChain {
+		/// Write `level` to `output`.
+		pub fn digital_write(&mut self, board: &mut Board, output: usize, level: bool) -> Result<()> {
+			let len = self.len();
+			let Some(byte) = self.state.get_mut(output / 8) else {
+				return Err(Error::PinOutOfBounds { pin: output as u8, len, source: "ShiftRegisterChain::digital_write".to_string() })
+			};
+
+			if level {
+				*byte |= 1 << (output % 8);
+			} else {
+				*byte &= !(1 << (output % 8));
+			}
+
+			self.update(board)
+		}
+
+		/// Write every output at once, one byte per register starting with the one wired to the board.
+		pub fn write_bytes(&mut self, board: &mut Board, bytes: &[u8]) -> Result<()> {
+			for (byte, value) in self.state.iter_mut().zip(bytes) {
+				*byte = *value;
+			}
+
+			self.update(board)
+		}
+
+		/// Shift the state into the chain, furthest register first, then latch it onto the outputs.
+		fn update(&self, board: &mut Board) -> Result<()> {
+			let data = self.state.iter().rev().copied().collect::<Vec<u8>>();
+
+			board.digital_write(self.latch_pin, false)?;
+			board.shift_out(self.data_pin, self.clock_pin, BitOrder::MsbFirst, &data)?;
+			board.digital_write(self.latch_pin, true)
+		}
+	}
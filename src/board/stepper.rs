@@ -0,0 +1,278 @@
+use crate::constants::{
+    ACCELSTEPPER_CONFIG,
+    ACCELSTEPPER_DATA,
+    ACCELSTEPPER_ENABLE,
+    ACCELSTEPPER_REPORT_POSITION,
+    ACCELSTEPPER_SET_ACCELERATION,
+    ACCELSTEPPER_SET_SPEED,
+    ACCELSTEPPER_STEP,
+    ACCELSTEPPER_STOP,
+    ACCELSTEPPER_TO,
+    ACCELSTEPPER_ZERO,
+    END_SYSEX,
+    MULTISTEPPER_CONFIG,
+    MULTISTEPPER_STOP,
+    MULTISTEPPER_TO,
+    START_SYSEX
+};
+use crate::encoding::{
+    encode_custom_float,
+    encode_i32
+};
+use crate::types::{
+    Error,
+    PinMode,
+    Result,
+    StepperConfig,
+};
+
+use super::Board;
+
+// stepper
+	impl Board {
+		/// Configure stepper `device` (0-9) according to `config`, placing its pins in `PinMode::Stepper`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn stepper_config(&mut self, device: u8, config: &StepperConfig) -> Result<()> {
+			let mut pins = config.interface.pins();
+			pins.extend(config.enable_pin);
+
+			for pin in &pins {
+				let Some(p) = self.pins.get(*pin as usize) else {
+					return Err(Error::PinOutOfBounds { pin: *pin, len: self.pins.len(), source: "stepper_config".to_string() })
+				};
+				if !p.modes.contains(&PinMode::Stepper) {
+					return Err(Error::InvalidPinMode { pin: *pin, mode: PinMode::Stepper, modes: p.modes.clone() });
+				}
+			}
+			for pin in &pins {
+				self.pins[*pin as usize].set_mode(PinMode::Stepper);
+			}
+
+			let interface = config.interface.wire_count() << 4
+				| (config.step_size as u8) << 1
+				| u8::from(config.enable_pin.is_some());
+
+			let mut buf = vec![
+				START_SYSEX,
+				ACCELSTEPPER_DATA,
+				ACCELSTEPPER_CONFIG,
+				device,
+				interface,
+			];
+			buf.extend(pins);
+			buf.push(config.invert_pins & 0x1F);
+			buf.push(END_SYSEX);
+
+			self.write_to_connection(&buf)
+		}
+
+		/// Set the current position of stepper `device` as its zero point.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn stepper_zero(&mut self, device: u8) -> Result<()> {
+			self.write_to_connection(&[START_SYSEX, ACCELSTEPPER_DATA, ACCELSTEPPER_ZERO, device, END_SYSEX])
+		}
+
+		/// Move stepper `device` by `steps` relative to its current position.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn stepper_step(&mut self, device: u8, steps: i32) -> Result<()> {
+			let mut buf = vec![START_SYSEX, ACCELSTEPPER_DATA, ACCELSTEPPER_STEP, device];
+			buf.extend(encode_i32(steps));
+			buf.push(END_SYSEX);
+
+			self.write_to_connection(&buf)
+		}
+
+		/// Move stepper `device` to the absolute `position`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn stepper_to(&mut self, device: u8, position: i32) -> Result<()> {
+			let mut buf = vec![START_SYSEX, ACCELSTEPPER_DATA, ACCELSTEPPER_TO, device];
+			buf.extend(encode_i32(position));
+			buf.push(END_SYSEX);
+
+			self.write_to_connection(&buf)
+		}
+
+		/// Enable or disable stepper `device` through its enable pin.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn stepper_enable(&mut self, device: u8, state: bool) -> Result<()> {
+			self.write_to_connection(&[START_SYSEX, ACCELSTEPPER_DATA, ACCELSTEPPER_ENABLE, device, u8::from(state), END_SYSEX])
+		}
+
+		/// Stop stepper `device` immediately. The board replies with a `Message::StepperMoveComplete`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn stepper_stop(&mut self, device: u8) -> Result<()> {
+			self.write_to_connection(&[START_SYSEX, ACCELSTEPPER_DATA, ACCELSTEPPER_STOP, device, END_SYSEX])
+		}
+
+		/// Ask for the position of stepper `device`, which arrives as a `Message::StepperPosition`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn stepper_report_position(&mut self, device: u8) -> Result<()> {
+			self.write_to_connection(&[START_SYSEX, ACCELSTEPPER_DATA, ACCELSTEPPER_REPORT_POSITION, device, END_SYSEX])
+		}
+
+		/// Set the `acceleration` of stepper `device` in steps per second per second.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn stepper_set_acceleration(&mut self, device: u8, acceleration: f32) -> Result<()> {
+			let mut buf = vec![START_SYSEX, ACCELSTEPPER_DATA, ACCELSTEPPER_SET_ACCELERATION, device];
+			buf.extend(encode_custom_float(acceleration));
+			buf.push(END_SYSEX);
+
+			self.write_to_connection(&buf)
+		}
+
+		/// Set the maximum `speed` of stepper `device` in steps per second.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn stepper_set_speed(&mut self, device: u8, speed: f32) -> Result<()> {
+			let mut buf = vec![START_SYSEX, ACCELSTEPPER_DATA, ACCELSTEPPER_SET_SPEED, device];
+			buf.extend(encode_custom_float(speed));
+			buf.push(END_SYSEX);
+
+			self.write_to_connection(&buf)
+		}
+	}
+
+// multi stepper
+	impl Board {
+		/// Gather the stepper `devices` into `group` (0-4) so they can be moved together.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn multi_stepper_config(&mut self, group: u8, devices: &[u8]) -> Result<()> {
+			let mut buf = vec![START_SYSEX, ACCELSTEPPER_DATA, MULTISTEPPER_CONFIG, group];
+			buf.extend(devices);
+			buf.push(END_SYSEX);
+
+			self.write_to_connection(&buf)
+		}
+
+		/// Move the steppers of `group` to the absolute `positions`, given in the order the devices were
+		/// configured. All steppers arrive at the same time, and the board replies with a
+		/// `Message::MultiStepperMoveComplete`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn multi_stepper_to(&mut self, group: u8, positions: &[i32]) -> Result<()> {
+			let mut buf = vec![START_SYSEX, ACCELSTEPPER_DATA, MULTISTEPPER_TO, group];
+			for position in positions {
+				buf.extend(encode_i32(*position));
+			}
+			buf.push(END_SYSEX);
+
+			self.write_to_connection(&buf)
+		}
+
+		/// Stop all steppers of `group` immediately.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn multi_stepper_stop(&mut self, group: u8) -> Result<()> {
+			self.write_to_connection(&[START_SYSEX, ACCELSTEPPER_DATA, MULTISTEPPER_STOP, group, END_SYSEX])
+		}
+	}
+
+/// A stepper motor driven by `AccelStepperFirmata` on a [`Board`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stepper {
+	device: u8,
+}
+
+// creation
+	impl Stepper {
+		/// Configure stepper `device` (0-9) on the `board` according to `config`.
+		pub fn attach(board: &mut Board, device: u8, config: &StepperConfig) -> Result<Stepper> {
+			board.stepper_config(device, config)?;
+			Ok(Stepper { device })
+		}
+	}
+
+// get
+	impl Stepper {
+		#[must_use]
+		/// Get the firmware device number of the stepper.
+		pub fn device(&self) -> u8 {
+			self.device
+		}
+	}
+
+// control
+	impl Stepper {
+		/// Set the current position as the zero point.
+		pub fn zero(&self, board: &mut Board) -> Result<()> {
+			board.stepper_zero(self.device)
+		}
+
+		/// Move by `steps` relative to the current position.
+		pub fn step(&self, board: &mut Board, steps: i32) -> Result<()> {
+			board.stepper_step(self.device, steps)
+		}
+
+		/// Move to the absolute `position`.
+		pub fn move_to(&self, board: &mut Board, position: i32) -> Result<()> {
+			board.stepper_to(self.device, position)
+		}
+
+		/// Enable or disable the motor.
+		pub fn enable(&self, board: &mut Board, state: bool) -> Result<()> {
+			board.stepper_enable(self.device, state)
+		}
+
+		/// Stop immediately.
+		pub fn stop(&self, board: &mut Board) -> Result<()> {
+			board.stepper_stop(self.device)
+		}
+
+		/// Ask for the current position.
+		pub fn report_position(&self, board: &mut Board) -> Result<()> {
+			board.stepper_report_position(self.device)
+		}
+
+		/// Set the acceleration in steps per second per second.
+		pub fn set_acceleration(&self, board: &mut Board, acceleration: f32) -> Result<()> {
+			board.stepper_set_acceleration(self.device, acceleration)
+		}
+
+		/// Set the maximum speed in steps per second.
+		pub fn set_speed(&self, board: &mut Board, speed: f32) -> Result<()> {
+			board.stepper_set_speed(self.device, speed)
+		}
+	}
+
+/// A group of [`Stepper`]s that move in coordination.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StepperGroup {
+	group: u8,
+	steppers: Vec<Stepper>,
+}
+
+// creation
+	impl StepperGroup {
+		/// Gather `steppers` into `group` (0-4) on the `board`.
+		pub fn new(board: &mut Board, group: u8, steppers: &[Stepper]) -> Result<StepperGroup> {
+			let devices = steppers.iter().map(Stepper::device).collect::<Vec<u8>>();
+			board.multi_stepper_config(group, &devices)?;
+			Ok(StepperGroup { group, steppers: steppers.to_vec() })
+		}
+	}
+
+// get
+	impl StepperGroup {
+		#[must_use]
+		/// Get the firmware group number.
+		pub fn group(&self) -> u8 {
+			self.group
+		}
+
+		#[must_use]
+		/// Get the steppers in the group.
+		pub fn steppers(&self) -> &[Stepper] {
+			&self.steppers
+		}
+	}
+
+// control
+	impl StepperGroup {
+		/// Move every stepper to its absolute position in `positions`, given in the order of the group's
+		/// steppers, so that they all arrive at the same time.
+		pub fn move_to(&self, board: &mut Board, positions: &[i32]) -> Result<()> {
+			board.multi_stepper_to(self.group, positions)
+		}
+
+		/// Stop every stepper in the group immediately.
+		pub fn stop(&self, board: &mut Board) -> Result<()> {
+			board.multi_stepper_stop(self.group)
+		}
+	}
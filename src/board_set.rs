@@ -0,0 +1,212 @@
+//! Several boards managed together, with pins addressed across all of them.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serialport::{
+	DataBits,
+	FlowControl,
+	Parity,
+	StopBits
+};
+
+use crate::types::{
+	BoardPinRef,
+	Error,
+	Message,
+	Pin,
+	PinMode,
+	Result,
+};
+use crate::Board;
+
+/// A message from one board of a [`BoardSet`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BoardMessage {
+	/// The id of the board that sent the message.
+	pub board: String,
+	pub message: Message,
+}
+
+/// A set of boards, each identified by an id such as its port or firmware name.
+#[derive(Debug, Default)]
+pub struct BoardSet {
+	boards: Vec<(String, Board)>,
+	aliases: HashMap<String, (String, u8)>,
+}
+
+/// Tag an error with the id of the board it came from.
+fn on_board(id: &str) -> impl FnOnce(Error) -> Error + '_ {
+	move |error| Error::Board { id: id.to_string(), error: Box::new(error) }
+}
+
+// creation
+	impl BoardSet {
+		#[must_use]
+		pub fn new() -> BoardSet {
+			BoardSet::default()
+		}
+
+		/// Add `board` to the set as `id`.
+		pub fn insert(&mut self, id: impl Into<String>, board: Board) -> Result<()> {
+			let id = id.into();
+			if self.get(&id).is_some() {
+				return Err(Error::DuplicateBoard { id });
+			}
+			self.boards.push((id, board));
+			Ok(())
+		}
+
+		/// Connect to the board on the serial port at `path` as with [`Board::connect`], and add it to the
+		/// set using the path as its id. A path already in the set is refused before the port is opened.
+		pub fn connect(&mut self, path: &str, baud_rate: u32, timeout: Duration) -> Result<()> {
+			if self.get(path).is_some() {
+				return Err(Error::DuplicateBoard { id: path.to_string() });
+			}
+			let board = Board::connect(serial_port_builder(path, baud_rate), timeout).map_err(on_board(path))?;
+			self.insert(path, board)
+		}
+
+		/// Connect to the board on the serial port at `path` as with [`Board::connect`], and add it to the
+		/// set using its firmware name as its id, which is returned. Boards running the same firmware are
+		/// told apart by a suffix, so the second `StandardFirmata.ino` is added as `StandardFirmata.ino#2`.
+		pub fn connect_by_firmware_name(&mut self, path: &str, baud_rate: u32, timeout: Duration) -> Result<String> {
+			let board = Board::connect(serial_port_builder(path, baud_rate), timeout).map_err(on_board(path))?;
+			let name = board.get_firmware_name().cloned().unwrap_or_default();
+
+			let id = std::iter::once(name.clone())
+				.chain((2..).map(|n| format!("{name}#{n}")))
+				.find(|id| self.get(id).is_none())
+				.expect("an unused id");
+			self.insert(id.clone(), board)?;
+			Ok(id)
+		}
+
+		/// Take the board with the specified `id` out of the set.
+		pub fn remove(&mut self, id: &str) -> Option<Board> {
+			let index = self.boards.iter().position(|(other, _)| other == id)?;
+			Some(self.boards.remove(index).1)
+		}
+
+		/// Name `pin` as `alias`, so that it can be addressed by the alias alone.
+		pub fn alias(&mut self, alias: impl Into<String>, pin: impl BoardPinRef) -> Result<()> {
+			let pin = pin.resolve(self)?;
+			self.aliases.insert(alias.into(), pin);
+			Ok(())
+		}
+	}
+
+// get
+	impl BoardSet {
+		#[must_use]
+		pub fn get(&self, id: &str) -> Option<&Board> {
+			self.boards.iter().find(|(other, _)| other == id).map(|(_, board)| board)
+		}
+
+		pub fn get_mut(&mut self, id: &str) -> Option<&mut Board> {
+			self.boards.iter_mut().find(|(other, _)| other == id).map(|(_, board)| board)
+		}
+
+		#[must_use]
+		/// Get the board id and pin index named by `alias`.
+		pub fn get_alias(&self, alias: &str) -> Option<&(String, u8)> {
+			self.aliases.get(alias)
+		}
+
+		/// Get the ids of the boards, in the order they were added.
+		pub fn ids(&self) -> impl Iterator<Item = &str> {
+			self.boards.iter().map(|(id, _)| id.as_str())
+		}
+
+		#[must_use]
+		pub fn len(&self) -> usize {
+			self.boards.len()
+		}
+
+		#[must_use]
+		pub fn is_empty(&self) -> bool {
+			self.boards.is_empty()
+		}
+
+		/// Get the current state of `pin`.
+		pub fn get_pin(&self, pin: impl BoardPinRef) -> Result<&Pin> {
+			let (id, pin) = pin.resolve(self)?;
+			let board = self.get(&id).ok_or_else(|| Error::UnknownBoard { id: id.clone() })?;
+			board.get_pin(pin as usize).ok_or_else(|| Error::Board {
+				id,
+				error: Box::new(Error::PinOutOfBounds { pin, len: board.get_all_pins().len(), source: "get_pin".to_string() }),
+			})
+		}
+	}
+
+// poll
+	impl BoardSet {
+		/// Poll every board, returning their messages tagged with the board they came from, along with the
+		/// errors from any boards that failed, tagged the same way. A failing board does not hold back the
+		/// messages of the others.
+		#[tracing::instrument(skip(self), ret, level = "DEBUG")]
+		pub fn poll(&mut self) -> (Vec<BoardMessage>, Vec<Error>) {
+			let mut messages = vec![];
+			let mut errors = vec![];
+
+			for (id, board) in &mut self.boards {
+				match board.poll() {
+					Ok(board_messages) => messages.extend(
+						board_messages.into_iter().map(|message| BoardMessage { board: id.clone(), message })
+					),
+					Err(error) => errors.push(on_board(id)(error)),
+				}
+			}
+
+			(messages, errors)
+		}
+	}
+
+// pins
+	impl BoardSet {
+		/// Run `f` with the board that `pin` is on and the pin's index on it, tagging any error with the
+		/// board's id.
+		pub fn with_pin<T>(&mut self, pin: impl BoardPinRef, f: impl FnOnce(&mut Board, u8) -> Result<T>) -> Result<T> {
+			let (id, pin) = pin.resolve(self)?;
+			let board = self.get_mut(&id).ok_or_else(|| Error::UnknownBoard { id: id.clone() })?;
+			f(board, pin).map_err(on_board(&id))
+		}
+
+		/// Set the `mode` of the specified `pin`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn set_pin_mode(&mut self, pin: impl BoardPinRef, mode: PinMode) -> Result<()> {
+			self.with_pin(pin, |board, pin| board.set_pin_mode(pin, mode))
+		}
+
+		/// Set the analog reporting `state` of the specified `pin`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn report_analog(&mut self, pin: impl BoardPinRef, state: bool) -> Result<()> {
+			self.with_pin(pin, |board, pin| board.report_analog(pin, state))
+		}
+
+		/// Set the digital reporting `state` of the specified `pin`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn report_digital(&mut self, pin: impl BoardPinRef, state: bool) -> Result<()> {
+			self.with_pin(pin, |board, pin| board.report_digital(pin, state))
+		}
+
+		/// Write `level` to the analog `pin`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn analog_write(&mut self, pin: impl BoardPinRef, level: u8) -> Result<()> {
+			self.with_pin(pin, |board, pin| board.analog_write(pin, level))
+		}
+
+		/// Write `level` to the digital `pin`.
+		#[tracing::instrument(skip(self), err, ret, level = "DEBUG")]
+		pub fn digital_write(&mut self, pin: impl BoardPinRef, level: bool) -> Result<()> {
+			self.with_pin(pin, |board, pin| board.digital_write(pin, level))
+		}
+	}
+
+fn serial_port_builder(path: &str, baud_rate: u32) -> serialport::SerialPortBuilder {
+	serialport::new(path, baud_rate)
+		.data_bits(DataBits::Eight)
+		.parity(Parity::None)
+		.stop_bits(StopBits::One)
+		.flow_control(FlowControl::None)
+}
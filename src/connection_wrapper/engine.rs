@@ -71,10 +71,12 @@ impl Engine {
 				match command {
 					Command::Halt => {
 						self.halt = true;
-						return;
 					}
 				}
 			}
+			if self.halt {
+				return;
+			}
 
 		//deal with outgoing data
 			let buffer = self.receiver.try_iter().flatten().collect::<Vec<u8>>();
@@ -107,7 +109,6 @@ impl Engine {
 				if let Err(error) = self.sender.send(buffer) {
 					tracing::warn!("{error}");
 					self.halt = true;
-					return;
 				}
 			}
 	}
@@ -13,7 +13,7 @@ use engine::Engine;
 
 #[derive(Debug)]
 pub struct ConnectionWrapper {
-	thread_handle: std::thread::JoinHandle<()>,
+	thread_handle: Option<std::thread::JoinHandle<()>>,
 	receiver: std::sync::mpsc::Receiver<Vec<u8>>,
 	sender: std::sync::mpsc::Sender<Vec<u8>>,
 	command_sender: std::sync::mpsc::Sender<Command>,
@@ -46,26 +46,57 @@ impl ConnectionWrapper {
 		});
 
 		ConnectionWrapper {
-			thread_handle,
+			thread_handle: Some(thread_handle),
 			receiver: from_engine_receiver,
 			sender: to_engine_sender,
 			command_sender: to_engine_command_sender,
 			error_receiver: from_engine_error_receiver
 		}
 	}
+
+	/// A connection without a serial port thread, for callers that move the bytes themselves. Returns
+	/// the connection along with the sender for bytes coming from the board and the receiver for bytes
+	/// going to it.
+	#[cfg(any(test, feature = "tokio"))]
+	pub fn detached() -> (ConnectionWrapper, std::sync::mpsc::Sender<Vec<u8>>, std::sync::mpsc::Receiver<Vec<u8>>) {
+		let (to_board_sender, to_board_receiver) = std::sync::mpsc::channel::<Vec<u8>>();
+		let (from_board_sender, from_board_receiver) = std::sync::mpsc::channel::<Vec<u8>>();
+		let (command_sender, _) = std::sync::mpsc::channel::<Command>();
+		let (_, error_receiver) = std::sync::mpsc::channel::<Error>();
+
+		let connection_wrapper = ConnectionWrapper {
+			thread_handle: None,
+			receiver: to_board_receiver,
+			sender: from_board_sender,
+			command_sender,
+			error_receiver,
+		};
+
+		(connection_wrapper, to_board_sender, from_board_receiver)
+	}
 }
 
 impl ConnectionWrapper {
 	pub fn is_active(&self) -> bool {
-		!self.thread_handle.is_finished()
+		!self.is_finished()
 	}
 	pub fn disconnect(&mut self) -> Result<()> {
-		if self.thread_handle.is_finished() {
+		if self.is_finished() {
 			return Err(Error::Disconnected);
 		}
+		if self.thread_handle.is_none() {
+			return Ok(());
+		}
 
 		self.command_sender.send(Command::Halt)?;
-		return Ok(());
+		Ok(())
+	}
+}
+
+impl ConnectionWrapper {
+	/// A detached connection has no thread to finish.
+	fn is_finished(&self) -> bool {
+		self.thread_handle.as_ref().is_some_and(std::thread::JoinHandle::is_finished)
 	}
 }
 
@@ -81,7 +112,7 @@ impl ConnectionWrapper {
 			}
 		}
 
-		if self.thread_handle.is_finished() {
+		if self.is_finished() {
 			return Err(Error::Disconnected);
 		}
 
@@ -100,7 +131,7 @@ impl ConnectionWrapper {
 			}
 		}
 
-		if self.thread_handle.is_finished() {
+		if self.is_finished() {
 			return Err(Error::Disconnected);
 		}
 		
@@ -42,6 +42,8 @@
     pub const SERIAL_DATA: u8 = 0x60;
     /// Reply with encoders current positions
     pub const ENCODER_DATA: u8 = 0x61;
+    /// Control a stepper motor through `AccelStepperFirmata`
+    pub const ACCELSTEPPER_DATA: u8 = 0x62;
     /// Set max angle, minPulse, maxPulse, freq
     pub const SERVO_CONFIG: u8 = 0x70;
     /// String message with 14-bits per char
@@ -120,6 +122,90 @@
     pub const I2C_READ_WRITE_MODE_MASK: u8 = 0x18;
     pub const I2C_10BIT_ADDRESS_MODE_MASK: u8 = 0x20;
     pub const I2C_END_TX_MASK: u8 = 0x40;
+    /// Register reported in replies to reads that did not specify one
+    pub const I2C_REGISTER_NOT_SPECIFIED: u8 = 0xFF;
+    /// First 7-bit address not reserved by the I2C specification
+    pub const I2C_SCAN_FIRST_ADDRESS: u8 = 0x08;
+    /// Last 7-bit address not reserved by the I2C specification
+    pub const I2C_SCAN_LAST_ADDRESS: u8 = 0x77;
+
+// AccelStepper additions.
+    pub const ACCELSTEPPER_CONFIG: u8 = 0x00;
+    pub const ACCELSTEPPER_ZERO: u8 = 0x01;
+    pub const ACCELSTEPPER_STEP: u8 = 0x02;
+    pub const ACCELSTEPPER_TO: u8 = 0x03;
+    pub const ACCELSTEPPER_ENABLE: u8 = 0x04;
+    pub const ACCELSTEPPER_STOP: u8 = 0x05;
+    pub const ACCELSTEPPER_REPORT_POSITION: u8 = 0x06;
+    pub const ACCELSTEPPER_SET_ACCELERATION: u8 = 0x08;
+    pub const ACCELSTEPPER_SET_SPEED: u8 = 0x09;
+    pub const ACCELSTEPPER_MOVE_COMPLETE: u8 = 0x0A;
+    pub const MULTISTEPPER_CONFIG: u8 = 0x20;
+    pub const MULTISTEPPER_TO: u8 = 0x21;
+    pub const MULTISTEPPER_STOP: u8 = 0x23;
+    pub const MULTISTEPPER_MOVE_COMPLETE: u8 = 0x24;
+
+// Encoder additions.
+    pub const ENCODER_ATTACH: u8 = 0x00;
+    pub const ENCODER_REPORT_POSITION: u8 = 0x01;
+    pub const ENCODER_REPORT_POSITIONS: u8 = 0x02;
+    pub const ENCODER_RESET_POSITION: u8 = 0x03;
+    pub const ENCODER_REPORT_AUTO: u8 = 0x04;
+    pub const ENCODER_DETACH: u8 = 0x05;
+    pub const ENCODER_DIRECTION_MASK: u8 = 0x40;
+    pub const ENCODER_CHANNEL_MASK: u8 = 0x3F;
+
+// OneWire additions.
+    pub const ONEWIRE_SEARCH_REQUEST: u8 = 0x40;
+    pub const ONEWIRE_CONFIG_REQUEST: u8 = 0x41;
+    pub const ONEWIRE_SEARCH_REPLY: u8 = 0x42;
+    pub const ONEWIRE_READ_REPLY: u8 = 0x43;
+    pub const ONEWIRE_SEARCH_ALARMS_REQUEST: u8 = 0x44;
+    pub const ONEWIRE_SEARCH_ALARMS_REPLY: u8 = 0x45;
+    pub const ONEWIRE_RESET_REQUEST_BIT: u8 = 0x01;
+    pub const ONEWIRE_SKIP_REQUEST_BIT: u8 = 0x02;
+    pub const ONEWIRE_SELECT_REQUEST_BIT: u8 = 0x04;
+    pub const ONEWIRE_READ_REQUEST_BIT: u8 = 0x08;
+    pub const ONEWIRE_DELAY_REQUEST_BIT: u8 = 0x10;
+    pub const ONEWIRE_WRITE_REQUEST_BIT: u8 = 0x20;
+
+// Serial additions.
+    pub const SERIAL_CONFIG: u8 = 0x10;
+    pub const SERIAL_WRITE: u8 = 0x20;
+    pub const SERIAL_READ: u8 = 0x30;
+    pub const SERIAL_REPLY: u8 = 0x40;
+    pub const SERIAL_CLOSE: u8 = 0x50;
+    pub const SERIAL_FLUSH: u8 = 0x60;
+    pub const SERIAL_LISTEN: u8 = 0x70;
+    pub const SERIAL_READ_CONTINUOUSLY: u8 = 0x00;
+    pub const SERIAL_STOP_READING: u8 = 0x01;
+    pub const SERIAL_COMMAND_MASK: u8 = 0xF0;
+    pub const SERIAL_PORT_ID_MASK: u8 = 0x0F;
+    pub const SERIAL_SOFTWARE_PORT_MASK: u8 = 0x08;
+    /// Data bytes sent per `SERIAL_WRITE`, keeping each message inside the firmware's sysex buffer
+    pub const SERIAL_WRITE_CHUNK_SIZE: usize = 30;
+
+// Scheduler additions.
+    pub const CREATE_FIRMATA_TASK: u8 = 0x00;
+    pub const DELETE_FIRMATA_TASK: u8 = 0x01;
+    pub const ADD_TO_FIRMATA_TASK: u8 = 0x02;
+    pub const DELAY_FIRMATA_TASK: u8 = 0x03;
+    pub const SCHEDULE_FIRMATA_TASK: u8 = 0x04;
+    pub const QUERY_ALL_FIRMATA_TASKS: u8 = 0x05;
+    pub const QUERY_FIRMATA_TASK: u8 = 0x06;
+    pub const RESET_FIRMATA_TASKS: u8 = 0x07;
+    pub const ERROR_TASK_REPLY: u8 = 0x08;
+    pub const QUERY_ALL_TASKS_REPLY: u8 = 0x09;
+    pub const QUERY_TASK_REPLY: u8 = 0x0A;
+    /// Task bytes sent per `ADD_TO_FIRMATA_TASK`, keeping each message inside the firmware's sysex buffer
+    pub const FIRMATA_TASK_CHUNK_SIZE: usize = 40;
+
+// Shift additions.
+    pub const SHIFT_OUT: u8 = 0x01;
+    pub const SHIFT_IN: u8 = 0x02;
+    pub const SHIFT_IN_REPLY: u8 = 0x03;
+    pub const SHIFT_LSB_FIRST: u8 = 0x00;
+    pub const SHIFT_MSB_FIRST: u8 = 0x01;
 
 // Other values
     /// Default analog resolution value
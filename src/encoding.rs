@@ -0,0 +1,200 @@
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_possible_wrap)]
+#![allow(clippy::cast_sign_loss)]
+
+//! Packing helpers for the multi-byte values used by the Firmata extensions, where every byte on
+//! the wire may only carry 7 bits.
+
+/// Encode a signed 32-bit integer into five 7-bit bytes, least significant first, with the sign
+/// carried in bit 3 of the last byte.
+pub fn encode_i32(value: i32) -> [u8; 5] {
+	let magnitude = value.unsigned_abs();
+
+	let mut bytes = [
+		magnitude as u8 & 0x7F,
+		(magnitude >> 7) as u8 & 0x7F,
+		(magnitude >> 14) as u8 & 0x7F,
+		(magnitude >> 21) as u8 & 0x7F,
+		(magnitude >> 28) as u8 & 0x07,
+	];
+
+	if value < 0 {
+		bytes[4] |= 0x08;
+	}
+
+	bytes
+}
+
+/// Decode five 7-bit bytes produced by [`encode_i32`].
+pub fn decode_i32(bytes: &[u8; 5]) -> i32 {
+	let magnitude = u32::from(bytes[0] & 0x7F)
+		| u32::from(bytes[1] & 0x7F) << 7
+		| u32::from(bytes[2] & 0x7F) << 14
+		| u32::from(bytes[3] & 0x7F) << 21
+		| u32::from(bytes[4] & 0x07) << 28;
+
+	if bytes[4] & 0x08 == 0 {
+		magnitude as i32
+	} else {
+		-(magnitude as i32)
+	}
+}
+
+/// Encode a float into the four byte Firmata custom float format: a 23-bit significand, a 4-bit
+/// base 10 exponent biased by 11 and a sign bit.
+pub fn encode_custom_float(value: f32) -> [u8; 4] {
+	const MAX_SIGNIFICAND: f64 = 8_388_608.0; // 2^23
+
+	if value == 0.0 || !value.is_finite() {
+		return [0, 0, 0, 0];
+	}
+
+	let sign = u8::from(value < 0.0);
+	let mut significand = f64::from(value.abs());
+
+	// move the decimal point until the significand is whole and the exponent fits, or as precise as it can be
+		let mut exponent = significand.log10().floor() as i32;
+		significand /= 10f64.powi(exponent);
+		while (significand.fract() != 0.0 || exponent > 4) && significand * 10.0 < MAX_SIGNIFICAND && exponent > -11 {
+			significand *= 10.0;
+			exponent -= 1;
+		}
+		while significand >= MAX_SIGNIFICAND || exponent < -11 {
+			significand /= 10.0;
+			exponent += 1;
+		}
+
+	let significand = (significand.round() as u32).min(0x7F_FFFF);
+	let exponent = (exponent + 11).clamp(0, 15) as u8;
+
+	[
+		significand as u8 & 0x7F,
+		(significand >> 7) as u8 & 0x7F,
+		(significand >> 14) as u8 & 0x7F,
+		(significand >> 21) as u8 & 0x03 | exponent << 2 | sign << 6,
+	]
+}
+
+/// Pack 8-bit `data` into a stream of 7-bit bytes, as used by the `OneWire` and scheduler extensions.
+pub fn encode_7bit(data: &[u8]) -> Vec<u8> {
+	let mut encoded = Vec::with_capacity(data.len() * 8 / 7 + 1);
+	let mut shift = 0;
+	let mut previous = 0u8;
+
+	for byte in data {
+		if shift == 0 {
+			encoded.push(byte & 0x7F);
+			shift += 1;
+			previous = byte >> 7;
+		} else {
+			encoded.push(((byte << shift) & 0x7F) | previous);
+			if shift == 6 {
+				encoded.push(byte >> 1);
+				shift = 0;
+			} else {
+				shift += 1;
+				previous = byte >> (8 - shift);
+			}
+		}
+	}
+
+	if shift > 0 {
+		encoded.push(previous);
+	}
+
+	encoded
+}
+
+/// Unpack a stream of 7-bit bytes produced by [`encode_7bit`].
+pub fn decode_7bit(encoded: &[u8]) -> Vec<u8> {
+	(0..encoded.len() * 7 / 8)
+		.map(|index| {
+			let bit = index * 8;
+			let position = bit / 7;
+			let shift = bit % 7;
+			let low = encoded[position] >> shift;
+			let high = encoded.get(position + 1).map_or(0, |byte| (u16::from(*byte) << (7 - shift)) as u8);
+			low | high
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// The value a custom float stands for, as the firmware reads it.
+	fn decode_custom_float(bytes: [u8; 4]) -> f64 {
+		let significand = u32::from(bytes[0]) | u32::from(bytes[1]) << 7 | u32::from(bytes[2]) << 14 | u32::from(bytes[3] & 0x03) << 21;
+		let exponent = i32::from(bytes[3] >> 2 & 0x0F) - 11;
+		let sign = if bytes[3] & 0x40 == 0 { 1.0 } else { -1.0 };
+		sign * f64::from(significand) * 10f64.powi(exponent)
+	}
+
+	#[test]
+	fn encode_i32_carries_the_sign_in_the_last_byte() {
+		assert_eq!(encode_i32(0), [0x00, 0x00, 0x00, 0x00, 0x00]);
+		assert_eq!(encode_i32(1), [0x01, 0x00, 0x00, 0x00, 0x00]);
+		assert_eq!(encode_i32(200), [0x48, 0x01, 0x00, 0x00, 0x00]);
+		assert_eq!(encode_i32(-200), [0x48, 0x01, 0x00, 0x00, 0x08]);
+		assert_eq!(encode_i32(i32::MAX), [0x7F, 0x7F, 0x7F, 0x7F, 0x07]);
+		assert_eq!(encode_i32(-i32::MAX), [0x7F, 0x7F, 0x7F, 0x7F, 0x0F]);
+	}
+
+	#[test]
+	fn encode_i32_round_trips() {
+		for value in [0, 1, -1, 127, -128, 16_384, -2_000_000, i32::MAX, -i32::MAX] {
+			assert_eq!(decode_i32(&encode_i32(value)), value);
+		}
+	}
+
+	#[test]
+	fn encode_custom_float_matches_known_values() {
+		assert_eq!(encode_custom_float(0.0), [0x00, 0x00, 0x00, 0x00]);
+		assert_eq!(encode_custom_float(1.0), [0x01, 0x00, 0x00, 0x2C]);
+		assert_eq!(encode_custom_float(0.5), [0x05, 0x00, 0x00, 0x28]);
+		assert_eq!(encode_custom_float(-2.5), [0x19, 0x00, 0x00, 0x68]);
+		assert_eq!(encode_custom_float(1000.0), [0x01, 0x00, 0x00, 0x38]);
+		assert_eq!(encode_custom_float(100_000.0), [0x0A, 0x00, 0x00, 0x3C]);
+	}
+
+	#[test]
+	fn encode_custom_float_round_trips() {
+		for value in [1.0f32, -1.0, 0.25, 12.75, 123.456, -987.5, 0.001, 50_000.0, 8_000_000.0] {
+			let decoded = decode_custom_float(encode_custom_float(value));
+			assert!((decoded - f64::from(value)).abs() <= f64::from(value).abs() * 1e-6, "{value} decoded as {decoded}");
+		}
+	}
+
+	#[test]
+	fn encode_custom_float_treats_non_finite_values_as_zero() {
+		assert_eq!(encode_custom_float(f32::NAN), [0x00, 0x00, 0x00, 0x00]);
+		assert_eq!(encode_custom_float(f32::INFINITY), [0x00, 0x00, 0x00, 0x00]);
+	}
+
+	#[test]
+	fn encode_7bit_packs_bits_least_significant_first() {
+		assert_eq!(encode_7bit(&[]), Vec::<u8>::new());
+		assert_eq!(encode_7bit(&[0xFF]), vec![0x7F, 0x01]);
+		assert_eq!(encode_7bit(&[0x01, 0x02, 0x03]), vec![0x01, 0x04, 0x0C, 0x00]);
+		assert_eq!(encode_7bit(&[0x80, 0x80]), vec![0x00, 0x01, 0x02]);
+		assert_eq!(encode_7bit(&[0xFF; 7]), vec![0x7F; 8]);
+	}
+
+	#[test]
+	fn decode_7bit_unpacks_encoded_bytes() {
+		assert_eq!(decode_7bit(&[0x7F, 0x01]), vec![0xFF]);
+		assert_eq!(decode_7bit(&[0x01, 0x04, 0x0C, 0x00]), vec![0x01, 0x02, 0x03]);
+		assert_eq!(decode_7bit(&[0x7F; 8]), vec![0xFF; 7]);
+	}
+
+	#[test]
+	fn encode_7bit_round_trips() {
+		let data = (0..=255).collect::<Vec<u8>>();
+		for len in 0..data.len() {
+			let encoded = encode_7bit(&data[..len]);
+			assert!(encoded.iter().all(|byte| byte & 0x80 == 0), "len {len}");
+			assert_eq!(decode_7bit(&encoded), &data[..len], "len {len}");
+		}
+	}
+}
@@ -0,0 +1,292 @@
+//! [`embedded-hal`](https://docs.rs/embedded-hal/1) implementations over a [`Board`], so that
+//! `embedded-hal` driver crates can be used with Firmata devices.
+//!
+//! The handles share a board through [`BoardAccess`], which is implemented for `RefCell<Board>` and
+//! `Mutex<Board>`.
+
+#![allow(clippy::cast_possible_truncation)]
+
+use std::cell::RefCell;
+use std::sync::{
+	Mutex,
+	PoisonError
+};
+use std::time::Duration;
+
+use embedded_hal::{
+	digital,
+	i2c,
+	pwm
+};
+
+use crate::types::{
+	Error,
+	I2CRequest,
+	Pin,
+	PinMode,
+	PinRef,
+	Result,
+};
+use crate::Board;
+
+/// Shared access to a [`Board`].
+pub trait BoardAccess {
+	/// Run `f` with exclusive access to the board.
+	fn with_board<T>(&self, f: impl FnOnce(&mut Board) -> T) -> T;
+}
+
+impl BoardAccess for RefCell<Board> {
+	fn with_board<T>(&self, f: impl FnOnce(&mut Board) -> T) -> T {
+		f(&mut self.borrow_mut())
+	}
+}
+
+impl BoardAccess for Mutex<Board> {
+	fn with_board<T>(&self, f: impl FnOnce(&mut Board) -> T) -> T {
+		f(&mut self.lock().unwrap_or_else(PoisonError::into_inner))
+	}
+}
+
+// errors
+	impl digital::Error for Error {
+		fn kind(&self) -> digital::ErrorKind {
+			digital::ErrorKind::Other
+		}
+	}
+
+	impl i2c::Error for Error {
+		fn kind(&self) -> i2c::ErrorKind {
+			match self {
+				Error::Timeout { .. } | Error::I2CShortRead { .. } => i2c::ErrorKind::NoAcknowledge(i2c::NoAcknowledgeSource::Unknown),
+				_ => i2c::ErrorKind::Other,
+			}
+		}
+	}
+
+	impl pwm::Error for Error {
+		fn kind(&self) -> pwm::ErrorKind {
+			pwm::ErrorKind::Other
+		}
+	}
+
+/// A digital output pin.
+#[derive(Debug)]
+pub struct OutputPin<'a, B: BoardAccess> {
+	board: &'a B,
+	pin: u8,
+}
+
+impl<'a, B: BoardAccess> OutputPin<'a, B> {
+	/// Place `pin` in `PinMode::Output`.
+	pub fn new(board: &'a B, pin: impl PinRef) -> Result<Self> {
+		let pin = board.with_board(|board| pin.resolve(board))?;
+		board.with_board(|board| board.set_pin_mode(pin, PinMode::Output))?;
+		Ok(Self { board, pin })
+	}
+}
+
+impl<B: BoardAccess> digital::ErrorType for OutputPin<'_, B> {
+	type Error = Error;
+}
+
+impl<B: BoardAccess> digital::OutputPin for OutputPin<'_, B> {
+	fn set_low(&mut self) -> Result<()> {
+		self.board.with_board(|board| board.digital_write(self.pin, false))
+	}
+
+	fn set_high(&mut self) -> Result<()> {
+		self.board.with_board(|board| board.digital_write(self.pin, true))
+	}
+}
+
+/// A digital input pin, which reads the value last reported by the board.
+#[derive(Debug)]
+pub struct InputPin<'a, B: BoardAccess> {
+	board: &'a B,
+	pin: u8,
+}
+
+impl<'a, B: BoardAccess> InputPin<'a, B> {
+	/// Place `pin` in `PinMode::Input` and turn on digital reporting for it.
+	pub fn new(board: &'a B, pin: impl PinRef) -> Result<Self> {
+		let pin = board.with_board(|board| pin.resolve(board))?;
+		board.with_board(|board| {
+			board.set_pin_mode(pin, PinMode::Input)?;
+			board.report_digital(pin, true)
+		})?;
+		Ok(Self { board, pin })
+	}
+
+	fn read(&self) -> Result<bool> {
+		self.board.with_board(|board| {
+			board.refresh()?;
+			Ok(board.get_pin(self.pin as usize).is_some_and(|pin| pin.value != 0))
+		})
+	}
+}
+
+impl<B: BoardAccess> digital::ErrorType for InputPin<'_, B> {
+	type Error = Error;
+}
+
+impl<B: BoardAccess> digital::InputPin for InputPin<'_, B> {
+	fn is_high(&mut self) -> Result<bool> {
+		self.read()
+	}
+
+	fn is_low(&mut self) -> Result<bool> {
+		self.read().map(|high| !high)
+	}
+}
+
+/// A PWM output pin.
+#[derive(Debug)]
+pub struct PwmPin<'a, B: BoardAccess> {
+	board: &'a B,
+	pin: u8,
+	max_duty_cycle: u16,
+}
+
+impl<'a, B: BoardAccess> PwmPin<'a, B> {
+	/// Place `pin` in `PinMode::Pwm`. The duty cycle runs up to the largest value at the board's PWM
+	/// resolution for the pin.
+	pub fn new(board: &'a B, pin: impl PinRef) -> Result<Self> {
+		let pin = board.with_board(|board| pin.resolve(board))?;
+		let max_duty_cycle = board.with_board(|board| {
+			board.set_pin_mode(pin, PinMode::Pwm)?;
+			Ok::<_, Error>(board.get_pin(pin as usize).map_or(u32::from(u8::MAX), Pin::get_max_value))
+		})?;
+		Ok(Self { board, pin, max_duty_cycle: max_duty_cycle.min(u32::from(u16::MAX)) as u16 })
+	}
+}
+
+impl<B: BoardAccess> pwm::ErrorType for PwmPin<'_, B> {
+	type Error = Error;
+}
+
+impl<B: BoardAccess> pwm::SetDutyCycle for PwmPin<'_, B> {
+	fn max_duty_cycle(&self) -> u16 {
+		self.max_duty_cycle
+	}
+
+	fn set_duty_cycle(&mut self, duty: u16) -> Result<()> {
+		self.board.with_board(|board| board.write_analog_value(self.pin, duty.min(self.max_duty_cycle)))
+	}
+}
+
+/// The board's I2C bus. Reads wait up to the bus timeout for the board to reply.
+#[derive(Debug)]
+pub struct I2c<'a, B: BoardAccess> {
+	board: &'a B,
+	timeout: Duration,
+}
+
+impl<'a, B: BoardAccess> I2c<'a, B> {
+	/// Enable I2C on the board, with reads waiting up to `timeout` for a reply.
+	pub fn new(board: &'a B, timeout: Duration) -> Result<Self> {
+		board.with_board(|board| board.i2c_config(0))?;
+		Ok(Self { board, timeout })
+	}
+
+	fn run(&mut self, address: u16, ten_bit_address: bool, operations: &mut [i2c::Operation<'_>]) -> Result<()> {
+		let timeout = self.timeout;
+
+		self.board.with_board(|board| {
+			let mut index = 0;
+			while index < operations.len() {
+				// a one byte write followed by a read is a register read, which the board does in one go
+					let register = match (&operations[index], operations.get(index + 1)) {
+						(i2c::Operation::Write([register]), Some(i2c::Operation::Read(_))) => {
+							index += 1;
+							Some(*register)
+						},
+						_ => None,
+					};
+
+				let restart = index + 1 < operations.len();
+
+				match &mut operations[index] {
+					i2c::Operation::Write(data) => {
+						board.i2c_request(&I2CRequest::write(address, data).ten_bit_address(ten_bit_address).restart(restart))?;
+					},
+					i2c::Operation::Read(buffer) => {
+						let size = u8::try_from(buffer.len())
+							.map_err(|_| Error::I2CReadTooLong { address, len: buffer.len(), max: usize::from(u8::MAX) })?;
+						let mut request = I2CRequest::read(address, size).ten_bit_address(ten_bit_address).restart(restart);
+						if let Some(register) = register {
+							request = request.register(register);
+						}
+
+						let reply = board.i2c_read_request(&request, timeout)?;
+						if reply.data.len() < buffer.len() {
+							return Err(Error::I2CShortRead { address, expected: buffer.len(), received: reply.data.len() });
+						}
+						buffer.copy_from_slice(&reply.data[..buffer.len()]);
+					},
+				}
+
+				index += 1;
+			}
+
+			Ok(())
+		})
+	}
+}
+
+impl<B: BoardAccess> i2c::ErrorType for I2c<'_, B> {
+	type Error = Error;
+}
+
+impl<B: BoardAccess> i2c::I2c<i2c::SevenBitAddress> for I2c<'_, B> {
+	fn transaction(&mut self, address: u8, operations: &mut [i2c::Operation<'_>]) -> Result<()> {
+		self.run(u16::from(address), false, operations)
+	}
+}
+
+impl<B: BoardAccess> i2c::I2c<i2c::TenBitAddress> for I2c<'_, B> {
+	fn transaction(&mut self, address: u16, operations: &mut [i2c::Operation<'_>]) -> Result<()> {
+		self.run(address, true, operations)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::cell::RefCell;
+	use std::time::Duration;
+
+	use embedded_hal::i2c::{
+		Error as _,
+		ErrorKind,
+		I2c as _,
+		NoAcknowledgeSource
+	};
+
+	use crate::board::board_with_devices;
+
+	use super::I2c;
+
+	#[test]
+	fn i2c_reads_with_and_without_a_register() {
+		let board = RefCell::new(board_with_devices(&[(0x40, &[0x10, 0x20, 0x30, 0x40])]));
+		let mut i2c = I2c::new(&board, Duration::from_secs(1)).unwrap();
+		let mut buffer = [0; 2];
+
+		i2c.read(0x40u8, &mut buffer).unwrap();
+		assert_eq!(buffer, [0x10, 0x20]);
+
+		i2c.write_read(0x40u8, &[0x01], &mut buffer).unwrap();
+		assert_eq!(buffer, [0x20, 0x30]);
+
+		i2c.write_read(0x40u8, &[0x02, 0x7F], &mut buffer).unwrap();
+		assert_eq!(buffer, [0x7F, 0x40]);
+	}
+
+	#[test]
+	fn i2c_read_from_an_absent_device_is_not_acknowledged() {
+		let board = RefCell::new(board_with_devices(&[]));
+		let mut i2c = I2c::new(&board, Duration::from_secs(1)).unwrap();
+
+		let error = i2c.read(0x40u8, &mut [0; 1]).unwrap_err();
+		assert_eq!(error.kind(), ErrorKind::NoAcknowledge(NoAcknowledgeSource::Unknown));
+	}
+}
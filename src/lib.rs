@@ -1,29 +1,63 @@
 mod constants;
+mod encoding;
 mod types;
 
 pub use types::{
+	AnalogPin,
+	AnalogPinInfo,
+	BitOrder,
+	BoardPinRef,
+	Endianness,
 	Error,
-	Message
+	HandshakeStage,
+	I2CBitfield,
+	I2CConfig,
+	I2CMode,
+	I2CReply,
+	I2CRegister,
+	I2CRequest,
+	Message,
+	OneWireRequest,
+	PinMode,
+	PinRef,
+	RegisterValue,
+	SchedulerTask,
+	SerialPortId,
+	StepSize,
+	StepperConfig,
+	StepperInterface
 };
 
-pub use constants::{
-	PIN_MODE_ANALOG,
-	PIN_MODE_ENCODER,
-	PIN_MODE_I2C,
-	PIN_MODE_IGNORE,
-	PIN_MODE_INPUT,
-	PIN_MODE_ONEWIRE,
-	PIN_MODE_OUTPUT,
-	PIN_MODE_PULLUP,
-	PIN_MODE_PWM,
-	PIN_MODE_SERIAL,
-	PIN_MODE_SERVO,
-	PIN_MODE_SHIFT,
-	PIN_MODE_STEPPER
-};
+#[cfg(feature = "embedded-hal")]
+pub mod hal;
+
+mod board_set;
+pub use board_set::{BoardMessage, BoardSet};
+
+mod shared_board;
+pub use shared_board::SharedBoard;
+
+#[cfg(feature = "tokio")]
+mod async_board;
+#[cfg(feature = "tokio")]
+pub use async_board::AsyncBoard;
 
 mod connection_wrapper;
 use connection_wrapper::ConnectionWrapper;
 
 mod board;
-pub use board::Board;
\ No newline at end of file
+pub use board::{
+	AnalogInput,
+	Board,
+	CallbackHandle,
+	DigitalInput,
+	DigitalOutput,
+	Ds18b20,
+	I2CDevice,
+	PwmOutput,
+	SerialStream,
+	Servo,
+	ShiftRegisterChain,
+	Stepper,
+	StepperGroup
+};
\ No newline at end of file
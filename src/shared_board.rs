@@ -0,0 +1,148 @@
+//! A [`Board`] that many threads can use at once, kept current by a background polling thread.
+
+use std::sync::mpsc::{
+	Receiver,
+	Sender
+};
+use std::sync::{
+	Arc,
+	Mutex,
+	MutexGuard,
+	PoisonError,
+	Weak
+};
+use std::time::Duration;
+
+use serialport::SerialPortBuilder;
+
+use crate::types::{
+	Error,
+	Message,
+	Pin,
+	PinRef,
+	Result,
+};
+use crate::Board;
+
+type Subscribers = Arc<Mutex<Vec<Sender<Message>>>>;
+
+/// A handle to a board shared between threads.
+///
+/// A background thread polls the board on an interval, so pin state stays current. The messages it
+/// decodes, including those set aside by blocking requests made through the handle, go to every
+/// receiver from [`SharedBoard::subscribe`], so the board should not be polled through `lock`. The
+/// thread stops once every handle has been dropped.
+#[derive(Clone, Debug)]
+pub struct SharedBoard {
+	board: Arc<Mutex<Board>>,
+	subscribers: Subscribers,
+}
+
+// creation
+	impl SharedBoard {
+		/// How often the background thread polls the board unless told otherwise.
+		pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+		/// Share `board`, polling it every `DEFAULT_POLL_INTERVAL`.
+		#[must_use]
+		pub fn new(board: Board) -> SharedBoard {
+			SharedBoard::with_poll_interval(board, SharedBoard::DEFAULT_POLL_INTERVAL)
+		}
+
+		/// Share `board`, polling it every `poll_interval`.
+		#[must_use]
+		pub fn with_poll_interval(board: Board, poll_interval: Duration) -> SharedBoard {
+			let board = Arc::new(Mutex::new(board));
+			let subscribers = Subscribers::default();
+			spawn_polling(Arc::downgrade(&board), Arc::clone(&subscribers), poll_interval);
+			SharedBoard { board, subscribers }
+		}
+
+		/// Connect to a board as with [`Board::connect`] and share it.
+		pub fn connect(serial_port_builder: SerialPortBuilder, timeout: Duration) -> Result<SharedBoard> {
+			Ok(SharedBoard::new(Board::connect(serial_port_builder, timeout)?))
+		}
+	}
+
+// access
+	impl SharedBoard {
+		/// Lock the board for exclusive use. Polling waits until the guard is dropped, so hold it only as
+		/// long as needed.
+		pub fn lock(&self) -> MutexGuard<'_, Board> {
+			self.board.lock().unwrap_or_else(PoisonError::into_inner)
+		}
+
+		/// Receive every message decoded from the board from now on. Dropping the receiver ends the
+		/// subscription.
+		pub fn subscribe(&self) -> Receiver<Message> {
+			let (sender, receiver) = std::sync::mpsc::channel();
+			self.subscribers.lock().unwrap_or_else(PoisonError::into_inner).push(sender);
+			receiver
+		}
+
+		/// Run `f` with the board locked, for issuing commands or blocking requests.
+		pub fn with_board<T>(&self, f: impl FnOnce(&mut Board) -> T) -> T {
+			f(&mut self.lock())
+		}
+
+		/// Get a copy of the current state of `pin`.
+		pub fn get_pin(&self, pin: impl PinRef) -> Result<Pin> {
+			let board = self.lock();
+			let pin = pin.resolve(&board)?;
+			board.get_pin(pin as usize).cloned().ok_or_else(|| Error::PinOutOfBounds {
+				pin,
+				len: board.get_all_pins().len(),
+				source: "SharedBoard::get_pin".to_string(),
+			})
+		}
+
+		/// Get a copy of the current state of every pin.
+		pub fn get_all_pins(&self) -> Vec<Pin> {
+			self.lock().get_all_pins().clone()
+		}
+	}
+
+/// The longest the polling thread waits between polls while the board keeps failing.
+const MAX_POLL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Poll the board every `poll_interval` until it has no handles left or has disconnected, handing the
+/// messages to the subscribers. While polls keep failing the interval doubles up to
+/// `MAX_POLL_BACKOFF`, and a repeated error is only logged once.
+fn spawn_polling(board: Weak<Mutex<Board>>, subscribers: Subscribers, poll_interval: Duration) {
+	std::thread::spawn(move || {
+		let mut interval = poll_interval;
+		let mut last_error = None;
+
+		while let Some(board) = board.upgrade() {
+			let result = board.lock().unwrap_or_else(PoisonError::into_inner).poll();
+			drop(board);
+
+			match result {
+				Ok(messages) => {
+					interval = poll_interval;
+					last_error = None;
+
+					if !messages.is_empty() {
+						subscribers.lock().unwrap_or_else(PoisonError::into_inner).retain(|sender| {
+							messages.iter().all(|message| sender.send(message.clone()).is_ok())
+						});
+					}
+				},
+				Err(error) if error.is_disconnected() => {
+					tracing::warn!("board disconnected, polling stopped");
+					break;
+				},
+				Err(error) => {
+					let error = error.to_string();
+					if last_error.as_ref() != Some(&error) {
+						tracing::warn!("poll error: {error}");
+						last_error = Some(error);
+					}
+					interval = (interval * 2).min(MAX_POLL_BACKOFF.max(poll_interval));
+				},
+			}
+
+			std::thread::sleep(interval);
+		}
+	});
+}
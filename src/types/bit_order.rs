@@ -0,0 +1,23 @@
+use crate::constants::{
+    SHIFT_LSB_FIRST,
+    SHIFT_MSB_FIRST
+};
+
+/// The order in which the bits of each byte are clocked through a shift register.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BitOrder {
+    #[default]
+    MsbFirst,
+    LsbFirst,
+}
+
+impl BitOrder {
+    /// The bit order as sent in a `SHIFT_DATA` request.
+    #[must_use]
+    pub fn as_byte(&self) -> u8 {
+        match self {
+            BitOrder::MsbFirst => SHIFT_MSB_FIRST,
+            BitOrder::LsbFirst => SHIFT_LSB_FIRST,
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use crate::BoardSet;
+
+use super::{
+    Error,
+    PinRef,
+    Result,
+};
+
+/// Anything that names a pin on one board of a [`BoardSet`]: a `(board, pin)` pair such as
+/// `("left", 13)` or `("left", "A0")`, or an alias configured with [`BoardSet::alias`].
+pub trait BoardPinRef: std::fmt::Debug {
+    /// Resolve to the id of the board in `set` and the index of the pin on it.
+    fn resolve(&self, set: &BoardSet) -> Result<(String, u8)>;
+}
+
+impl<P: PinRef> BoardPinRef for (&str, P) {
+    fn resolve(&self, set: &BoardSet) -> Result<(String, u8)> {
+        let board = set.get(self.0).ok_or_else(|| Error::UnknownBoard { id: self.0.to_string() })?;
+        let pin = PinRef::resolve(&self.1, board).map_err(|error| Error::Board { id: self.0.to_string(), error: Box::new(error) })?;
+        Ok((self.0.to_string(), pin))
+    }
+}
+
+impl<P: PinRef> BoardPinRef for (String, P) {
+    fn resolve(&self, set: &BoardSet) -> Result<(String, u8)> {
+        BoardPinRef::resolve(&(self.0.as_str(), &self.1), set)
+    }
+}
+
+impl BoardPinRef for str {
+    fn resolve(&self, set: &BoardSet) -> Result<(String, u8)> {
+        set.get_alias(self).cloned().ok_or_else(|| Error::UnknownPin { name: self.to_string() })
+    }
+}
+
+impl BoardPinRef for String {
+    fn resolve(&self, set: &BoardSet) -> Result<(String, u8)> {
+        BoardPinRef::resolve(self.as_str(), set)
+    }
+}
+
+impl<T: BoardPinRef + ?Sized> BoardPinRef for &T {
+    fn resolve(&self, set: &BoardSet) -> Result<(String, u8)> {
+        BoardPinRef::resolve(&**self, set)
+    }
+}
@@ -3,6 +3,7 @@ use std::sync::mpsc::SendError;
 use serialport::Error as SerialPortError;
 
 use super::super::connection_wrapper::Command;
+use super::{HandshakeStage, PinMode};
 
 /// Firmata error type.
 #[derive(Debug)]
@@ -25,19 +26,44 @@ pub enum Error {
 	/// Mpsc Command `SendError`
 	MpscCommandSend(SendError<Command>),
 	/// Invalid Pin Mode
-	InvalidPinMode { pin: u8, modes: Vec<u8> },
+	InvalidPinMode { pin: u8, mode: PinMode, modes: Vec<PinMode> },
+	/// A pin is not in a mode that allows the operation
+	WrongPinMode { pin: u8, mode: PinMode, expected: Vec<PinMode> },
+	/// A pin handle was used with a board other than the one that created it
+	ForeignHandle { pin: u8 },
     /// Pin out of bounds
     PinOutOfBounds { pin: u8, len: usize, source: String },
     /// Serialport Error
-	Serialport(SerialPortError)
+	Serialport(SerialPortError),
+	/// No reply arrived in time
+	Timeout { source: String },
+	/// A `OneWire` device replied with data that failed its CRC check
+	OneWireCrcMismatch { address: u64 },
+	/// An I2C device returned fewer bytes than were asked for
+	I2CShortRead { address: u16, expected: usize, received: usize },
+	/// An I2C read asked for more bytes than a single request can carry
+	I2CReadTooLong { address: u16, len: usize, max: usize },
+	/// A servo pulse too short to be told apart from an angle
+	ServoPulseTooShort { pin: u8, micros: u16, min: u16 },
+	/// The board has no pins that support a feature
+	UnsupportedFeature { feature: String },
+	/// A pin name that does not match any pin on the board
+	UnknownPin { name: String },
+	/// The board did not complete a stage of the connection handshake in time
+	HandshakeTimeout { stage: HandshakeStage },
+	/// A board id that does not match any board in the set
+	UnknownBoard { id: String },
+	/// A board id that is already used by another board in the set
+	DuplicateBoard { id: String },
+	/// An error from one board in a set
+	Board { id: String, error: Box<Error> },
 }
 
 impl Error {
 	pub fn is_disconnected(&self) -> bool {
-		if let Error::Disconnected = self {
-			true
-		} else {
-			false
+		match self {
+			Error::Board { error, .. } => error.is_disconnected(),
+			error => matches!(error, Error::Disconnected),
 		}
 	}
 }
@@ -54,13 +80,45 @@ impl std::fmt::Display for Error {
 			Error::Utf8(error) => write!(f, "UTF8 error: {error}"),
 			Error::MpscBufSend(error) => write!(f, "Mpsc Buf SendError error: {error}"),
 			Error::MpscCommandSend(error) => write!(f, "Mpsc Command SendError error: {error}"),
-			Error::InvalidPinMode { pin, modes } => write!(f, "Invalid Pin Mode: {pin} modes: {modes:?}"),
+			Error::InvalidPinMode { pin, mode, modes } => {
+				let modes = modes.iter().map(PinMode::to_string).collect::<Vec<String>>().join(", ");
+				write!(f, "Invalid Pin Mode: {mode} for pin {pin} modes: [{modes}]")
+			},
+			Error::WrongPinMode { pin, mode, expected } => {
+				let expected = expected.iter().map(PinMode::to_string).collect::<Vec<String>>().join(", ");
+				write!(f, "Wrong Pin Mode: pin {pin} is {mode}, expected one of: [{expected}]")
+			},
+			Error::ForeignHandle { pin } => write!(f, "Handle for pin {pin} used with a board that did not create it"),
 			Error::PinOutOfBounds { pin, len, source } => write!(f, "Pin out of bounds: {pin} ({len}) source: {source}"),
 			Error::Serialport(error) => write!(f, "Serialport Error: {error}"),
+			Error::Timeout { source } => write!(f, "Timed out waiting for a reply: {source}"),
+			Error::OneWireCrcMismatch { address } => write!(f, "OneWire CRC mismatch from device: {address:016X}"),
+			Error::I2CShortRead { address, expected, received } => write!(f, "I2C short read from device {address:#04X}: expected {expected} bytes, received {received}"),
+			Error::I2CReadTooLong { address, len, max } => write!(f, "I2C read from device {address:#04X} too long: {len} bytes, at most {max} per request"),
+			Error::ServoPulseTooShort { pin, micros, min } => write!(f, "Servo pulse on pin {pin} too short: {micros}us, at least {min}us can be written as a pulse"),
+			Error::UnsupportedFeature { feature } => write!(f, "Board does not support: {feature}"),
+			Error::UnknownPin { name } => write!(f, "Unknown pin: {name}"),
+			Error::HandshakeTimeout { stage } => write!(f, "Handshake timed out waiting for the {stage}"),
+			Error::UnknownBoard { id } => write!(f, "Unknown board: {id}"),
+			Error::DuplicateBoard { id } => write!(f, "Duplicate board: {id}, add it under another id"),
+			Error::Board { id, error } => write!(f, "Board {id}: {error}"),
 		}
 	}
 }
 
+impl From<Error> for std::io::Error {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::StdIo(error) => error,
+            Error::Timeout { .. } => std::io::Error::new(std::io::ErrorKind::TimedOut, error),
+            Error::Disconnected => std::io::Error::new(std::io::ErrorKind::NotConnected, error),
+            error => std::io::Error::other(error),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
 impl From<std::io::Error> for Error {
     fn from(error: std::io::Error) -> Self {
         Error::StdIo(error)
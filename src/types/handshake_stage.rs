@@ -0,0 +1,20 @@
+/// A stage of the handshake performed by [`Board::connect`](crate::Board::connect).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HandshakeStage {
+    /// Waiting for the firmware name and version, which any Firmata sketch sends.
+    Firmware,
+    /// Waiting for the pin capabilities.
+    Capabilities,
+    /// Waiting for the analog mapping.
+    AnalogMapping,
+}
+
+impl std::fmt::Display for HandshakeStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HandshakeStage::Firmware => write!(f, "firmware report (is the port right and the sketch Firmata?)"),
+            HandshakeStage::Capabilities => write!(f, "capability response"),
+            HandshakeStage::AnalogMapping => write!(f, "analog mapping response"),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+#![allow(clippy::cast_possible_truncation)]
+
+use crate::constants::{
+    END_SYSEX,
+    I2C_CONFIG,
+    START_SYSEX,
+    SYSEX_REALTIME
+};
+
+/// The I2C settings sent with `I2C_CONFIG`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct I2CConfig {
+    delay: u16,
+    user_data: Vec<u8>,
+}
+
+// creation
+impl I2CConfig {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+// options
+impl I2CConfig {
+    /// Wait `delay` microseconds between writing a register and reading it back, for devices that
+    /// need time before the data is ready. The delay is 14 bits wide.
+    #[must_use]
+    pub fn delay(mut self, delay: u16) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Firmware-defined data sent after the delay, such as the power pin settings some boards use to
+    /// power their I2C devices.
+    #[must_use]
+    pub fn user_data(mut self, data: &[u8]) -> Self {
+        self.user_data = data.to_vec();
+        self
+    }
+}
+
+// get
+impl I2CConfig {
+    #[must_use]
+    pub fn get_delay(&self) -> u16 {
+        self.delay
+    }
+
+    #[must_use]
+    pub fn get_user_data(&self) -> &[u8] {
+        &self.user_data
+    }
+}
+
+// encoding
+impl I2CConfig {
+    /// The full `I2C_CONFIG` sysex message.
+    #[must_use]
+    pub fn to_sysex(&self) -> Vec<u8> {
+        let mut buf = vec![
+            START_SYSEX,
+            I2C_CONFIG,
+            self.delay as u8 & SYSEX_REALTIME,
+            (self.delay >> 7) as u8 & SYSEX_REALTIME,
+        ];
+
+        buf.extend(self.user_data.iter().map(|datum| datum & SYSEX_REALTIME));
+        buf.push(END_SYSEX);
+
+        buf
+    }
+}
@@ -0,0 +1,170 @@
+#![allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::cast_possible_wrap)]
+
+use std::marker::PhantomData;
+
+/// The order in which a register's bytes are sent over the bus.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Endianness {
+    /// Most significant byte first, as most sensors do.
+    #[default]
+    Big,
+    Little,
+}
+
+/// A value that fits in an I2C register.
+pub trait RegisterValue: Copy {
+    /// Width of the register in bytes.
+    const WIDTH: usize;
+
+    /// Build the value from its bits.
+    fn from_bits(bits: u32) -> Self;
+
+    /// The bits of the value.
+    fn to_bits(self) -> u32;
+
+    /// Decode the value from the first `WIDTH` bytes of `bytes`.
+    #[must_use]
+    fn from_bytes(bytes: &[u8], endianness: Endianness) -> Self {
+        let bytes = &bytes[..Self::WIDTH];
+        let bits = match endianness {
+            Endianness::Big => bytes.iter().fold(0, |bits, byte| bits << 8 | u32::from(*byte)),
+            Endianness::Little => bytes.iter().rev().fold(0, |bits, byte| bits << 8 | u32::from(*byte)),
+        };
+        Self::from_bits(bits)
+    }
+
+    /// Encode the value into `WIDTH` bytes.
+    fn to_bytes(self, endianness: Endianness) -> Vec<u8> {
+        let bits = self.to_bits();
+        let little = (0..Self::WIDTH).map(|index| (bits >> (8 * index)) as u8);
+        match endianness {
+            Endianness::Big => little.rev().collect(),
+            Endianness::Little => little.collect(),
+        }
+    }
+}
+
+macro_rules! register_value {
+    ($type:ty, $unsigned:ty) => {
+        impl RegisterValue for $type {
+            const WIDTH: usize = std::mem::size_of::<$type>();
+
+            fn from_bits(bits: u32) -> Self {
+                bits as $unsigned as $type
+            }
+
+            fn to_bits(self) -> u32 {
+                u32::from(self as $unsigned)
+            }
+        }
+    };
+}
+
+register_value!(u8, u8);
+register_value!(i8, u8);
+register_value!(u16, u16);
+register_value!(i16, u16);
+register_value!(u32, u32);
+register_value!(i32, u32);
+
+/// A register of an I2C device, holding a `T`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct I2CRegister<T> {
+    address: u8,
+    endianness: Endianness,
+    value: PhantomData<T>,
+}
+
+impl<T> Clone for I2CRegister<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for I2CRegister<T> {}
+
+// creation
+impl<T: RegisterValue> I2CRegister<T> {
+    /// The big endian register at `address`.
+    #[must_use]
+    pub const fn new(address: u8) -> Self {
+        Self { address, endianness: Endianness::Big, value: PhantomData }
+    }
+
+    /// Send the register's bytes least significant first.
+    #[must_use]
+    pub const fn little_endian(mut self) -> Self {
+        self.endianness = Endianness::Little;
+        self
+    }
+
+    /// The bits `offset..offset + width` of the register.
+    ///
+    /// # Panics
+    /// If the field is empty or does not fit within the register, which fails the build when the
+    /// field is a constant.
+    #[must_use]
+    pub const fn bitfield(self, offset: u8, width: u8) -> I2CBitfield<T> {
+        assert!(width > 0 && offset as usize + width as usize <= T::WIDTH * 8, "bitfield must fit within its register");
+        I2CBitfield { register: self, offset, width }
+    }
+}
+
+// get
+impl<T> I2CRegister<T> {
+    #[must_use]
+    pub fn get_address(&self) -> u8 {
+        self.address
+    }
+
+    #[must_use]
+    pub fn get_endianness(&self) -> Endianness {
+        self.endianness
+    }
+}
+
+/// A run of bits within an I2C register.
+#[derive(Debug, PartialEq, Eq)]
+pub struct I2CBitfield<T> {
+    register: I2CRegister<T>,
+    offset: u8,
+    width: u8,
+}
+
+impl<T> Clone for I2CBitfield<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for I2CBitfield<T> {}
+
+// get
+impl<T> I2CBitfield<T> {
+    #[must_use]
+    pub fn get_register(&self) -> I2CRegister<T> {
+        self.register
+    }
+
+    /// The mask of the field's bits, in place within the register.
+    #[must_use]
+    pub fn mask(&self) -> u32 {
+        let bits = if self.width >= 32 { u32::MAX } else { (1 << self.width) - 1 };
+        bits << self.offset
+    }
+}
+
+// encoding
+impl<T: RegisterValue> I2CBitfield<T> {
+    /// Extract the field from the register's `value`.
+    #[must_use]
+    pub fn extract(&self, value: T) -> u32 {
+        (value.to_bits() & self.mask()) >> self.offset
+    }
+
+    /// Replace the field in the register's `value` with `field`, discarding bits that do not fit.
+    #[must_use]
+    pub fn insert(&self, value: T, field: u32) -> T {
+        T::from_bits(value.to_bits() & !self.mask() | (field << self.offset) & self.mask())
+    }
+}
@@ -1,7 +1,7 @@
 /// An I2C reply.
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct I2CReply {
-    pub address: u8,
+    pub address: u16,
     pub register: u8,
     pub data: Vec<u8>,
 }
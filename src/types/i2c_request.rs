@@ -0,0 +1,229 @@
+#![allow(clippy::cast_possible_truncation)]
+
+use crate::constants::{
+    END_SYSEX,
+    I2C_10BIT_ADDRESS_MODE_MASK,
+    I2C_END_TX_MASK,
+    I2C_READ,
+    I2C_READ_CONTINUOUSLY,
+    I2C_REQUEST,
+    I2C_STOP_READING,
+    I2C_WRITE,
+    START_SYSEX,
+    SYSEX_REALTIME
+};
+
+/// What an I2C request asks of the device.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum I2CMode {
+    #[default]
+    Write,
+    Read,
+    ReadContinuously,
+    StopReading,
+}
+
+impl I2CMode {
+    /// The read/write bits of the request's mode byte.
+    #[must_use]
+    pub fn as_bits(&self) -> u8 {
+        match self {
+            I2CMode::Write => I2C_WRITE << 3,
+            I2CMode::Read => I2C_READ << 3,
+            I2CMode::ReadContinuously => I2C_READ_CONTINUOUSLY,
+            I2CMode::StopReading => I2C_STOP_READING,
+        }
+    }
+}
+
+/// An I2C request, covering register addressing, 10-bit addresses and whether the transmission ends
+/// with a stop or a repeated start.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct I2CRequest {
+    address: u16,
+    mode: I2CMode,
+    ten_bit_address: bool,
+    restart: bool,
+    register: Option<u8>,
+    size: u8,
+    data: Vec<u8>,
+}
+
+// creation
+impl I2CRequest {
+    /// Write `data` to the device at `address`.
+    #[must_use]
+    pub fn write(address: u16, data: &[u8]) -> Self {
+        Self::new(address, I2CMode::Write).with_data(data)
+    }
+
+    /// Read `size` bytes from the device at `address`.
+    #[must_use]
+    pub fn read(address: u16, size: u8) -> Self {
+        Self::new(address, I2CMode::Read).with_size(size)
+    }
+
+    /// Read `size` bytes from the device at `address` on every sampling interval.
+    #[must_use]
+    pub fn read_continuously(address: u16, size: u8) -> Self {
+        Self::new(address, I2CMode::ReadContinuously).with_size(size)
+    }
+
+    /// Stop all continuous reads from the device at `address`.
+    #[must_use]
+    pub fn stop_reading(address: u16) -> Self {
+        Self::new(address, I2CMode::StopReading)
+    }
+
+    fn new(address: u16, mode: I2CMode) -> Self {
+        Self {
+            address,
+            mode,
+            ten_bit_address: address > 0x7F,
+            ..Default::default()
+        }
+    }
+
+    fn with_data(mut self, data: &[u8]) -> Self {
+        self.data = data.to_vec();
+        self
+    }
+
+    fn with_size(mut self, size: u8) -> Self {
+        self.size = size;
+        self
+    }
+}
+
+// options
+impl I2CRequest {
+    /// Write `register` to the device before reading from it, or before the data of a write.
+    #[must_use]
+    pub fn register(mut self, register: u8) -> Self {
+        self.register = Some(register);
+        self
+    }
+
+    /// Treat the address as a 10-bit address. This is the default for addresses above 0x7F.
+    #[must_use]
+    pub fn ten_bit_address(mut self, state: bool) -> Self {
+        self.ten_bit_address = state;
+        self
+    }
+
+    /// End the transmission with a repeated start rather than a stop, keeping hold of the bus.
+    #[must_use]
+    pub fn restart(mut self, state: bool) -> Self {
+        self.restart = state;
+        self
+    }
+}
+
+// get
+impl I2CRequest {
+    #[must_use]
+    pub fn get_address(&self) -> u16 {
+        self.address
+    }
+
+    #[must_use]
+    pub fn get_mode(&self) -> I2CMode {
+        self.mode
+    }
+
+    #[must_use]
+    pub fn get_register(&self) -> Option<u8> {
+        self.register
+    }
+}
+
+// encoding
+impl I2CRequest {
+    /// The full `I2C_REQUEST` sysex message.
+    #[must_use]
+    pub fn to_sysex(&self) -> Vec<u8> {
+        let mut mode = self.mode.as_bits();
+        if self.ten_bit_address {
+            mode |= I2C_10BIT_ADDRESS_MODE_MASK | (self.address >> 7) as u8 & 0x07;
+        }
+        if self.restart {
+            mode |= I2C_END_TX_MASK;
+        }
+
+        let mut buf = vec![
+            START_SYSEX,
+            I2C_REQUEST,
+            self.address as u8 & SYSEX_REALTIME,
+            mode,
+        ];
+
+        let payload = match self.mode {
+            I2CMode::Write => self.register.into_iter().chain(self.data.iter().copied()).collect::<Vec<u8>>(),
+            I2CMode::Read | I2CMode::ReadContinuously => self.register.into_iter().chain([self.size]).collect(),
+            I2CMode::StopReading => vec![],
+        };
+
+        for datum in payload {
+            buf.push(datum & SYSEX_REALTIME);
+            buf.push(datum >> 7);
+        }
+
+        buf.push(END_SYSEX);
+
+        buf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_sends_register_and_data_as_7bit_pairs() {
+        assert_eq!(
+            I2CRequest::write(0x09, &[0x6F, 0xFF]).register(0x10).to_sysex(),
+            vec![START_SYSEX, I2C_REQUEST, 0x09, 0x00, 0x10, 0x00, 0x6F, 0x00, 0x7F, 0x01, END_SYSEX],
+        );
+    }
+
+    #[test]
+    fn read_sends_register_then_size() {
+        assert_eq!(
+            I2CRequest::read(0x68, 6).register(0x3B).to_sysex(),
+            vec![START_SYSEX, I2C_REQUEST, 0x68, 0x08, 0x3B, 0x00, 0x06, 0x00, END_SYSEX],
+        );
+        assert_eq!(
+            I2CRequest::read(0x68, 200).to_sysex(),
+            vec![START_SYSEX, I2C_REQUEST, 0x68, 0x08, 0x48, 0x01, END_SYSEX],
+        );
+    }
+
+    #[test]
+    fn continuous_and_stop_reading_set_their_mode_bits() {
+        assert_eq!(
+            I2CRequest::read_continuously(0x48, 2).to_sysex(),
+            vec![START_SYSEX, I2C_REQUEST, 0x48, 0x10, 0x02, 0x00, END_SYSEX],
+        );
+        assert_eq!(
+            I2CRequest::stop_reading(0x48).to_sysex(),
+            vec![START_SYSEX, I2C_REQUEST, 0x48, 0x18, END_SYSEX],
+        );
+    }
+
+    #[test]
+    fn ten_bit_address_carries_its_high_bits_in_the_mode_byte() {
+        assert_eq!(
+            I2CRequest::read(0x2A5, 1).to_sysex(),
+            vec![START_SYSEX, I2C_REQUEST, 0x25, 0x08 | 0x20 | 0x05, 0x01, 0x00, END_SYSEX],
+        );
+        assert_eq!(
+            I2CRequest::read(0x25, 1).ten_bit_address(true).to_sysex()[3],
+            0x08 | 0x20,
+        );
+    }
+
+    #[test]
+    fn restart_sets_the_end_tx_bit() {
+        assert_eq!(I2CRequest::write(0x09, &[]).restart(true).to_sysex()[3], 0x40);
+    }
+}
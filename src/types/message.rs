@@ -1,21 +1,36 @@
+use super::{SchedulerTask, SerialPortId};
+
 /// Received Firmata message
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Message {
     ProtocolVersion(u8, u8),
-    Analog(Vec<(u8, u8)>),
+    Analog(Vec<(u8, u16)>),
     Digital(Vec<(u8, bool)>),
     EmptyResponse,
     AnalogMappingResponse,
     CapabilityResponse,
-    PinStateResponse,
+    PinStateResponse { pin: Option<u8> },
     ReportFirmwareName(String),
     ReportFirmwareVersion(String),
+    StringData(String),
     I2CReply,
+    StepperPosition { device: u8, position: i32 },
+    StepperMoveComplete { device: u8, position: i32 },
+    MultiStepperMoveComplete { group: u8 },
+    Encoder { id: u8, position: i32 },
+    OneWireSearchReply { pin: u8, addresses: Vec<u64> },
+    OneWireSearchAlarmsReply { pin: u8, addresses: Vec<u64> },
+    OneWireReadReply { pin: u8, correlation_id: u16, data: Vec<u8> },
+    SerialReply { port: SerialPortId, data: Vec<u8> },
+    SchedulerTasks(Vec<u8>),
+    SchedulerTaskReply { id: u8, task: Option<SchedulerTask> },
+    SchedulerTaskError(SchedulerTask),
+    ShiftInReply { data_pin: u8, data: Vec<u8> },
 }
 
 impl Message {
     #[must_use]
-    pub fn try_as_analog(&self) -> Option<&Vec<(u8, u8)>> {
+    pub fn try_as_analog(&self) -> Option<&Vec<(u8, u16)>> {
         if let Message::Analog(data) = self {
             Some(data)
         } else {
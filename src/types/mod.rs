@@ -1,11 +1,35 @@
+mod bit_order;
+mod board_pin_ref;
 mod error;
+mod handshake_stage;
+mod i2c_config;
+mod i2c_register;
 mod i2c_reply;
+mod i2c_request;
 mod message;
+mod onewire_request;
 mod pin;
+mod pin_mode;
+mod pin_ref;
 mod result;
+mod scheduler_task;
+mod serial_port_id;
+mod stepper_config;
 
+pub use bit_order::BitOrder;
+pub use board_pin_ref::BoardPinRef;
 pub use error::Error;
+pub use handshake_stage::HandshakeStage;
+pub use i2c_config::I2CConfig;
+pub use i2c_register::{Endianness, I2CBitfield, I2CRegister, RegisterValue};
 pub use i2c_reply::I2CReply;
+pub use i2c_request::{I2CMode, I2CRequest};
 pub use message::Message;
+pub use onewire_request::OneWireRequest;
 pub use pin::Pin;
-pub use result::Result;
\ No newline at end of file
+pub use pin_mode::PinMode;
+pub use pin_ref::{AnalogPin, AnalogPinInfo, PinRef};
+pub use result::Result;
+pub use scheduler_task::SchedulerTask;
+pub use serial_port_id::SerialPortId;
+pub use stepper_config::{StepSize, StepperConfig, StepperInterface};
\ No newline at end of file
@@ -0,0 +1,19 @@
+/// A `OneWire` request, made up of the steps the board should take on the bus in order: reset, skip
+/// or select, write, delay and read.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OneWireRequest {
+    /// Reset the bus before anything else.
+    pub reset: bool,
+    /// Address all devices on the bus.
+    pub skip: bool,
+    /// Address the device with this ROM address.
+    pub select: Option<u64>,
+    /// Bytes to write.
+    pub write: Vec<u8>,
+    /// Milliseconds for the board to wait after writing.
+    pub delay: u32,
+    /// Number of bytes to read.
+    pub read: u16,
+    /// Identifier echoed back in the reply to a read.
+    pub correlation_id: u16,
+}
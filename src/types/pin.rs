@@ -1,37 +1,59 @@
-use crate::constants::{
-    DEFAULT_ANALOG_RESOLUTION,
-    PIN_MODE_ANALOG
-};
+use std::collections::HashMap;
+
+use crate::constants::DEFAULT_ANALOG_RESOLUTION;
+
+use super::PinMode;
 
 /// The current state and configuration of a pin.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Pin {
 	/// Whether this is an analog pin or not.
 	pub analog: bool,
+	/// The analog channel of the pin, if it is an analog pin.
+	pub analog_channel: Option<u8>,
     /// Currently configured mode.
-    pub mode: u8,
+    pub mode: PinMode,
     /// All pin modes.
-    pub modes: Vec<u8>,
+    pub modes: Vec<PinMode>,
 	/// The report analog state.
 	pub report_analog_active: bool,
 	/// The report digital state.
 	pub report_digital_active: bool,
-    /// Current resolution.
+    /// Resolution of the currently configured mode, in bits.
     pub resolution: u8,
+    /// Resolution of each supported mode, in bits.
+    pub resolutions: HashMap<PinMode, u8>,
     /// Pin value.
-    pub value: u8,
+    pub value: u16,
 }
 
 impl Pin {
     pub fn default_with_report_digital_active() -> Self {
         Self {
 			analog: false,
-            mode: PIN_MODE_ANALOG,
-            modes: vec![PIN_MODE_ANALOG],
+			analog_channel: None,
+            mode: PinMode::Analog,
+            modes: vec![PinMode::Analog],
 			report_analog_active: false,
 			report_digital_active: true,
             resolution: DEFAULT_ANALOG_RESOLUTION,
+            resolutions: HashMap::from([(PinMode::Analog, DEFAULT_ANALOG_RESOLUTION)]),
             value: 0,
         }
     }
+
+    /// Set the currently configured `mode`, along with the resolution that goes with it.
+    pub(crate) fn set_mode(&mut self, mode: PinMode) {
+        self.mode = mode;
+        if let Some(resolution) = self.resolutions.get(&mode) {
+            self.resolution = *resolution;
+        }
+    }
+
+    #[must_use]
+    /// Get the largest value the pin takes in its current mode.
+    pub fn get_max_value(&self) -> u32 {
+        let resolution = self.resolutions.get(&self.mode).copied().unwrap_or(self.resolution);
+        (1u32 << resolution.clamp(1, 31)) - 1
+    }
 }
\ No newline at end of file
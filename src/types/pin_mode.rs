@@ -0,0 +1,138 @@
+use crate::constants::{
+    PIN_MODE_ANALOG,
+    PIN_MODE_ENCODER,
+    PIN_MODE_I2C,
+    PIN_MODE_IGNORE,
+    PIN_MODE_INPUT,
+    PIN_MODE_ONEWIRE,
+    PIN_MODE_OUTPUT,
+    PIN_MODE_PULLUP,
+    PIN_MODE_PWM,
+    PIN_MODE_SERIAL,
+    PIN_MODE_SERVO,
+    PIN_MODE_SHIFT,
+    PIN_MODE_STEPPER,
+    SYSEX_REALTIME
+};
+
+use super::Error;
+
+/// The mode of a pin.
+///
+/// Modes compare and hash by their code, so an `Unknown` carrying the code of a known mode, such as
+/// `Unknown(1)`, is the same mode as `Output`.
+#[derive(Clone, Copy, Debug)]
+pub enum PinMode {
+    /// Digital input.
+    Input,
+    /// Digital output.
+    Output,
+    /// Analog input.
+    Analog,
+    /// PWM output.
+    Pwm,
+    /// Servo output.
+    Servo,
+    /// Shift register data or clock.
+    Shift,
+    /// Part of the I2C bus.
+    I2C,
+    /// `OneWire` bus.
+    OneWire,
+    /// Stepper motor output.
+    Stepper,
+    /// Rotary encoder input.
+    Encoder,
+    /// Serial port rx or tx.
+    Serial,
+    /// Digital input with the internal pull-up resistor enabled.
+    Pullup,
+    /// Ignored by digital writes and capability responses.
+    Ignore,
+    /// A mode this crate does not know about, such as one added by custom firmware.
+    Unknown(u8),
+}
+
+impl From<PinMode> for u8 {
+    fn from(mode: PinMode) -> Self {
+        match mode {
+            PinMode::Input => PIN_MODE_INPUT,
+            PinMode::Output => PIN_MODE_OUTPUT,
+            PinMode::Analog => PIN_MODE_ANALOG,
+            PinMode::Pwm => PIN_MODE_PWM,
+            PinMode::Servo => PIN_MODE_SERVO,
+            PinMode::Shift => PIN_MODE_SHIFT,
+            PinMode::I2C => PIN_MODE_I2C,
+            PinMode::OneWire => PIN_MODE_ONEWIRE,
+            PinMode::Stepper => PIN_MODE_STEPPER,
+            PinMode::Encoder => PIN_MODE_ENCODER,
+            PinMode::Serial => PIN_MODE_SERIAL,
+            PinMode::Pullup => PIN_MODE_PULLUP,
+            PinMode::Ignore => PIN_MODE_IGNORE,
+            PinMode::Unknown(byte) => byte,
+        }
+    }
+}
+
+impl PartialEq for PinMode {
+    fn eq(&self, other: &Self) -> bool {
+        u8::from(*self) == u8::from(*other)
+    }
+}
+
+impl Eq for PinMode {}
+
+impl std::hash::Hash for PinMode {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        u8::from(*self).hash(state);
+    }
+}
+
+impl TryFrom<u8> for PinMode {
+    type Error = Error;
+
+    /// Modes are sent as 7-bit values, so only bytes above 0x7F are rejected.
+    fn try_from(byte: u8) -> Result<Self, Self::Error> {
+        Ok(match byte {
+            PIN_MODE_INPUT => PinMode::Input,
+            PIN_MODE_OUTPUT => PinMode::Output,
+            PIN_MODE_ANALOG => PinMode::Analog,
+            PIN_MODE_PWM => PinMode::Pwm,
+            PIN_MODE_SERVO => PinMode::Servo,
+            PIN_MODE_SHIFT => PinMode::Shift,
+            PIN_MODE_I2C => PinMode::I2C,
+            PIN_MODE_ONEWIRE => PinMode::OneWire,
+            PIN_MODE_STEPPER => PinMode::Stepper,
+            PIN_MODE_ENCODER => PinMode::Encoder,
+            PIN_MODE_SERIAL => PinMode::Serial,
+            PIN_MODE_PULLUP => PinMode::Pullup,
+            PIN_MODE_IGNORE => PinMode::Ignore,
+            byte if byte <= SYSEX_REALTIME => PinMode::Unknown(byte),
+            byte => return Err(Error::BadByte(byte)),
+        })
+    }
+}
+
+impl std::fmt::Display for PinMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PinMode::Input => write!(f, "INPUT"),
+            PinMode::Output => write!(f, "OUTPUT"),
+            PinMode::Analog => write!(f, "ANALOG"),
+            PinMode::Pwm => write!(f, "PWM"),
+            PinMode::Servo => write!(f, "SERVO"),
+            PinMode::Shift => write!(f, "SHIFT"),
+            PinMode::I2C => write!(f, "I2C"),
+            PinMode::OneWire => write!(f, "ONEWIRE"),
+            PinMode::Stepper => write!(f, "STEPPER"),
+            PinMode::Encoder => write!(f, "ENCODER"),
+            PinMode::Serial => write!(f, "SERIAL"),
+            PinMode::Pullup => write!(f, "PULLUP"),
+            PinMode::Ignore => write!(f, "IGNORE"),
+            PinMode::Unknown(byte) => match PinMode::try_from(*byte) {
+                Ok(PinMode::Unknown(_)) | Err(_) => write!(f, "UNKNOWN({byte:#04X})"),
+                Ok(mode) => mode.fmt(f),
+            },
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use crate::Board;
+
+use super::{
+    Error,
+    Result,
+};
+
+/// An analog input channel, such as `AnalogPin(0)` for `A0`, resolved to a pin index through the
+/// board's analog mapping.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AnalogPin(pub u8);
+
+/// An analog pin as reported by the board's analog mapping.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AnalogPinInfo {
+    /// The pin's name, such as `A0`.
+    pub name: String,
+    /// The pin's index.
+    pub pin: u8,
+    /// The pin's analog channel.
+    pub channel: u8,
+}
+
+/// Anything that names a pin: a pin index, an [`AnalogPin`], or a name such as `"A0"` or `"13"`.
+pub trait PinRef: std::fmt::Debug {
+    /// Resolve to the index of the pin on `board`.
+    fn resolve(&self, board: &Board) -> Result<u8>;
+}
+
+impl PinRef for u8 {
+    fn resolve(&self, _board: &Board) -> Result<u8> {
+        Ok(*self)
+    }
+}
+
+impl PinRef for AnalogPin {
+    fn resolve(&self, board: &Board) -> Result<u8> {
+        board
+            .analog_pins()
+            .into_iter()
+            .find(|info| info.channel == self.0)
+            .map(|info| info.pin)
+            .ok_or_else(|| Error::UnknownPin { name: format!("A{}", self.0) })
+    }
+}
+
+impl PinRef for str {
+    fn resolve(&self, board: &Board) -> Result<u8> {
+        let unknown = || Error::UnknownPin { name: self.to_string() };
+
+        if let Some(channel) = self.strip_prefix(['A', 'a']) {
+            AnalogPin(channel.parse().map_err(|_| unknown())?).resolve(board)
+        } else {
+            self.parse().map_err(|_| unknown())
+        }
+    }
+}
+
+impl PinRef for String {
+    fn resolve(&self, board: &Board) -> Result<u8> {
+        self.as_str().resolve(board)
+    }
+}
+
+impl<T: PinRef + ?Sized> PinRef for &T {
+    fn resolve(&self, board: &Board) -> Result<u8> {
+        (**self).resolve(board)
+    }
+}
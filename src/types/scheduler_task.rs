@@ -0,0 +1,32 @@
+/// A task stored in the board's scheduler.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SchedulerTask {
+    /// Task id.
+    pub id: u8,
+    /// Board time in milliseconds at which the task next runs.
+    pub time_ms: u32,
+    /// Length of the task data.
+    pub length: u16,
+    /// Position within the task data the task will continue from.
+    pub position: u16,
+    /// The recorded Firmata messages that make up the task.
+    pub data: Vec<u8>,
+}
+
+impl SchedulerTask {
+    /// Parse a task from the decoded payload of a task reply.
+    #[must_use]
+    pub fn from_reply(id: u8, payload: &[u8]) -> Option<Self> {
+        let time_ms = u32::from_le_bytes(payload.get(0..4)?.try_into().ok()?);
+        let length = u16::from_le_bytes(payload.get(4..6)?.try_into().ok()?);
+        let position = u16::from_le_bytes(payload.get(6..8)?.try_into().ok()?);
+
+        Some(Self {
+            id,
+            time_ms,
+            length,
+            position,
+            data: payload[8..].to_vec(),
+        })
+    }
+}
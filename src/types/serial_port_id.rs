@@ -0,0 +1,34 @@
+use crate::constants::{
+    SERIAL_PORT_ID_MASK,
+    SERIAL_SOFTWARE_PORT_MASK
+};
+
+/// A serial port on the board, as used by the serial passthrough.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SerialPortId {
+    /// Hardware UART 0 to 3.
+    Hardware(u8),
+    /// Software serial port 0 to 3.
+    Software(u8),
+}
+
+impl SerialPortId {
+    /// The port id as sent in the lower nibble of a serial command.
+    #[must_use]
+    pub fn as_byte(&self) -> u8 {
+        match self {
+            SerialPortId::Hardware(port) => port & 0x03,
+            SerialPortId::Software(port) => SERIAL_SOFTWARE_PORT_MASK | (port & 0x03),
+        }
+    }
+
+    #[must_use]
+    pub fn from_byte(byte: u8) -> Self {
+        let byte = byte & SERIAL_PORT_ID_MASK;
+        if byte & SERIAL_SOFTWARE_PORT_MASK == 0 {
+            SerialPortId::Hardware(byte & 0x03)
+        } else {
+            SerialPortId::Software(byte & 0x03)
+        }
+    }
+}
@@ -0,0 +1,70 @@
+/// How a stepper motor is wired to the board.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StepperInterface {
+    /// A step/direction driver board.
+    Driver { step_pin: u8, direction_pin: u8 },
+    /// A two wire motor.
+    TwoWire { pins: [u8; 2] },
+    /// A three wire motor.
+    ThreeWire { pins: [u8; 3] },
+    /// A four wire motor.
+    FourWire { pins: [u8; 4] },
+}
+
+impl StepperInterface {
+    /// The wire count as used in the upper bits of the interface byte.
+    #[must_use]
+    pub fn wire_count(&self) -> u8 {
+        match self {
+            StepperInterface::Driver { .. } => 1,
+            StepperInterface::TwoWire { .. } => 2,
+            StepperInterface::ThreeWire { .. } => 3,
+            StepperInterface::FourWire { .. } => 4,
+        }
+    }
+
+    /// The motor pins, in the order the firmware expects them.
+    #[must_use]
+    pub fn pins(&self) -> Vec<u8> {
+        match self {
+            StepperInterface::Driver { step_pin, direction_pin } => vec![*step_pin, *direction_pin],
+            StepperInterface::TwoWire { pins } => pins.to_vec(),
+            StepperInterface::ThreeWire { pins } => pins.to_vec(),
+            StepperInterface::FourWire { pins } => pins.to_vec(),
+        }
+    }
+}
+
+/// The size of a single step.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StepSize {
+    #[default]
+    Whole = 0,
+    Half = 1,
+    Quarter = 2,
+}
+
+/// The configuration of an `AccelStepperFirmata` stepper motor.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StepperConfig {
+    /// How the motor is wired.
+    pub interface: StepperInterface,
+    /// The size of a single step.
+    pub step_size: StepSize,
+    /// An optional pin which enables the motor or driver.
+    pub enable_pin: Option<u8>,
+    /// Bit flags of pins to invert: motor pins 1 to 4 in bits 0 to 3, the enable pin in bit 4.
+    pub invert_pins: u8,
+}
+
+impl StepperConfig {
+    #[must_use]
+    pub fn new(interface: StepperInterface) -> Self {
+        Self {
+            interface,
+            step_size: StepSize::default(),
+            enable_pin: None,
+            invert_pins: 0,
+        }
+    }
+}